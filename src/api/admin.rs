@@ -0,0 +1,372 @@
+use crate::{
+    api::responses::{
+        types::{ErrorResponse, ResimulateGameResponse},
+        ErrorMessage, ResimulateGame,
+    },
+    db::{entities::GameState, DbConnection},
+    dex::Dex,
+};
+use rocket::{
+    http::Status,
+    request::{FromRequest, Outcome},
+    response::status,
+    serde::json::Json,
+    Request, State,
+};
+use uuid::Uuid;
+
+const ADMIN_TOKEN_HEADER: &str = "X-Admin-Token";
+
+//Request guard for the admin endpoints. The token is set like the database settings, as admin_token.
+//Without it being set, the admin endpoints can't be used at all.
+pub(crate) struct Admin;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = &'static str;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Ok(admin_token) = request
+            .rocket()
+            .figment()
+            .extract_inner::<String>("admin_token")
+        else {
+            return Outcome::Error((
+                Status::Unauthorized,
+                "The admin endpoints are disabled, because no admin token is configured.",
+            ));
+        };
+        match request.headers().get_one(ADMIN_TOKEN_HEADER) {
+            Some(token) if token == admin_token => Outcome::Success(Admin),
+            _ => Outcome::Error((
+                Status::Unauthorized,
+                "A valid admin token is needed for this request.",
+            )),
+        }
+    }
+}
+
+#[post("/admin/games/<id>/resimulate")]
+pub(crate) async fn resimulate_game(
+    id: &str,
+    admin: Result<Admin, &'static str>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<ResimulateGameResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!(
+        "{} | Received resimulate game request for id: {}",
+        trace_id,
+        id
+    );
+    if let Err(message) = admin {
+        log::info!("{} | {}", trace_id, message);
+        return Err(status::Custom(
+            Status::Unauthorized,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from(message),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    }
+    let game = match db.get_game(id).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    if game.state != GameState::Finished {
+        log::info!("{} | Game with id {} is not finished", trace_id, id);
+        return Err(status::Custom(
+            Status::Conflict,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("Only finished games can be resimulated."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    }
+    match game.resimulate(dex) {
+        Ok(resimulated) => {
+            let recorded_winner = game.battle.as_ref().and_then(|battle| battle.winner());
+            let matches = game.battle.as_ref() == Some(&resimulated);
+            if matches {
+                log::info!("{} | Resimulation of game {} matches", trace_id, id);
+            } else {
+                log::error!("{} | Resimulation of game {} diverged", trace_id, id);
+            }
+            Ok(status::Custom(
+                Status::Ok,
+                Json(ResimulateGame {
+                    trace_id,
+                    seed: game.seed,
+                    matches,
+                    recorded_winner,
+                    resimulated_winner: resimulated.winner(),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message());
+            Err(status::Custom(
+                Status::UnprocessableEntity,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message(),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{stats::Stats, team::CreatureSet, Action};
+    use crate::db::entities::Game;
+    use crate::{api::responses, db::entities::GameState, dex::Dex, *};
+    use rocket::{
+        http::{Header, Status},
+        local::asynchronous::Client,
+    };
+    use testcontainers_modules::{
+        surrealdb,
+        testcontainers::{runners::AsyncRunner, ImageExt},
+    };
+
+    const SURREALDB_VERSION: &str = "v2.0.4";
+    const ADMIN_TOKEN: &str = "admin-secret";
+
+    fn admin_rocket() -> rocket::Rocket<rocket::Build> {
+        rocket::build().configure(rocket::Config::figment().merge(("admin_token", ADMIN_TOKEN)))
+    }
+
+    fn finished_game(dex: &Dex) -> Game {
+        let mut game = Game::default();
+        game.add_player();
+        game.join(dex, 0);
+        for (side, (species, move_id)) in [("pebblon", "rock-throw"), ("stormhawk", "spark")]
+            .into_iter()
+            .enumerate()
+        {
+            let team = vec![CreatureSet {
+                species: String::from(species),
+                nickname: None,
+                level: 50,
+                ability: None,
+                item: None,
+                moves: vec![String::from(move_id)],
+                investment: Stats::default(),
+                nature: None,
+            }];
+            game.submit_team(dex, side, team, 0).unwrap();
+        }
+        for side in 0..2 {
+            game.submit_lead_order(dex, side, vec![0], 0).unwrap();
+        }
+        while game.state == GameState::Ongoing {
+            game.submit_action(
+                dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+                0,
+            )
+            .unwrap();
+            game.submit_action(
+                dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+                0,
+            )
+            .unwrap();
+        }
+        game
+    }
+
+    #[rocket::async_test]
+    async fn test_resimulating_finished_game() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = admin_rocket();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let dex = client.rocket().state::<Dex>().unwrap();
+        let created_game: Game = db
+            .conn
+            .create("games")
+            .content(finished_game(dex))
+            .await
+            .expect("Creating game failed.")
+            .expect("");
+
+        let response = client
+            .post(uri!(super::resimulate_game(created_game.id.id.to_string())))
+            .header(Header::new("X-Admin-Token", ADMIN_TOKEN))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::ResimulateGame>()
+            .await
+            .expect("Invalid response from server.");
+        assert!(response.matches);
+        assert_eq!(response.recorded_winner, Some(0));
+        assert_eq!(response.resimulated_winner, Some(0));
+    }
+
+    #[rocket::async_test]
+    async fn test_resimulating_unfinished_game() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = admin_rocket();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let created_game: Game = db
+            .conn
+            .create("games")
+            .content(Game::default())
+            .await
+            .expect("Creating game failed.")
+            .expect("");
+
+        let response = client
+            .post(uri!(super::resimulate_game(created_game.id.id.to_string())))
+            .header(Header::new("X-Admin-Token", ADMIN_TOKEN))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Conflict);
+    }
+
+    #[rocket::async_test]
+    async fn test_resimulating_needs_the_admin_token() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = admin_rocket();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let dex = client.rocket().state::<Dex>().unwrap();
+        let created_game: Game = db
+            .conn
+            .create("games")
+            .content(finished_game(dex))
+            .await
+            .expect("Creating game failed.")
+            .expect("");
+
+        let without_token = client
+            .post(uri!(super::resimulate_game(created_game.id.id.to_string())))
+            .dispatch()
+            .await;
+        let wrong_token = client
+            .post(uri!(super::resimulate_game(created_game.id.id.to_string())))
+            .header(Header::new("X-Admin-Token", "guessed"))
+            .dispatch()
+            .await;
+
+        assert_eq!(without_token.status(), Status::Unauthorized);
+        assert_eq!(wrong_token.status(), Status::Unauthorized);
+    }
+
+    #[rocket::async_test]
+    async fn test_admin_endpoints_are_disabled_without_a_configured_token() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+
+        let response = client
+            .post(uri!(super::resimulate_game("anything")))
+            .header(Header::new("X-Admin-Token", ""))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
@@ -0,0 +1,942 @@
+use crate::{
+    api::{
+        requests::{SubmitActionRequest, SubmitLeadOrderRequest, SubmitTeamRequest},
+        responses::{
+            types::{
+                ErrorResponse, GetBattleResponse, GetTeamPreviewResponse, SubmitActionResponse,
+                SubmitLeadOrderResponse, SubmitTeamResponse,
+            },
+            ErrorMessage, GetBattle, GetTeamPreview, SubmitAction, SubmitLeadOrder, SubmitTeam,
+        },
+    },
+    battle::{team::CreatureSet, BattleError},
+    db::DbConnection,
+    dex::Dex,
+};
+use rocket::{http::Status, response::status, serde::json::Json, State};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+pub(crate) fn unix_time() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn battle_error_status(err: &BattleError) -> Status {
+    match err {
+        BattleError::NotStarted
+        | BattleError::BattleOver
+        | BattleError::AlreadySubmitted
+        | BattleError::NotInPreview
+        | BattleError::LockedIn => Status::Conflict,
+        BattleError::InvalidAction(_)
+        | BattleError::InvalidTeam(_)
+        | BattleError::RuleViolations(_) => Status::UnprocessableEntity,
+    }
+}
+
+//The player id of a game only exists for that game, so any saved team can be brought into it.
+async fn submitted_team(
+    trace_id: Uuid,
+    request: SubmitTeamRequest,
+    db: &DbConnection,
+) -> Result<Vec<CreatureSet>, ErrorResponse> {
+    let team_id = match (request.team, request.team_id) {
+        (Some(team), None) => return Ok(team),
+        (None, Some(team_id)) => team_id,
+        _ => {
+            log::error!("{} | Neither or both of team and team_id given", trace_id);
+            return Err(status::Custom(
+                Status::BadRequest,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: String::from("Submit either a team or the id of a saved team."),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let saved = db.get_team(&team_id).await.map_err(|err| {
+        log::error!("{} | {}", trace_id, err.message);
+        status::Custom(
+            err.status_code,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: err.message,
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        )
+    })?;
+    Ok(saved.creatures)
+}
+
+#[put("/games/<id>/team", format = "json", data = "<request>")]
+pub(crate) async fn submit_team(
+    id: &str,
+    request: Json<SubmitTeamRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<SubmitTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received submit team request for id: {}", trace_id, id);
+    let mut game = match db.get_current_game(id, dex, unix_time()).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let request = request.into_inner();
+    let Some(side) = game.side_of(&request.player_id) else {
+        log::error!("{} | Player is not part of game {}", trace_id, id);
+        return Err(status::Custom(
+            Status::Forbidden,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("You are not a player of this game."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    let team = submitted_team(trace_id, request, db).await?;
+    let preview_started = match game.submit_team(dex, side, team, unix_time()) {
+        Ok(preview_started) => preview_started,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message());
+            let status = battle_error_status(&err);
+            let error_message = err.message();
+            let errors = match err {
+                BattleError::RuleViolations(errors) => errors,
+                _ => Vec::new(),
+            };
+            return Err(status::Custom(
+                status,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message,
+                    error_code: errors.first().map(|error| error.error_code),
+                    errors,
+                }),
+            ));
+        }
+    };
+    match db.update_game(game).await {
+        Ok(_) => {
+            log::info!(
+                "{} | Submitted team for side {} of game {}",
+                trace_id,
+                side,
+                id
+            );
+            Ok(status::Custom(
+                Status::Ok,
+                Json(SubmitTeam {
+                    trace_id,
+                    preview_started,
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/games/<id>/preview?<player_id>")]
+pub(crate) async fn get_team_preview(
+    id: &str,
+    player_id: &str,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<GetTeamPreviewResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!(
+        "{} | Received get team preview request for id: {}",
+        trace_id,
+        id
+    );
+    let game = match db.get_current_game(id, dex, unix_time()).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let Some(side) = game.side_of(player_id) else {
+        log::error!("{} | Player is not part of game {}", trace_id, id);
+        return Err(status::Custom(
+            Status::Forbidden,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("You are not a player of this game."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    //Until both teams are in, the deadline is the one for submitting them.
+    let Some(deadline) = game.preview_deadline.or(game.team_deadline) else {
+        log::info!("{} | Team preview of game {} has not started", trace_id, id);
+        return Err(status::Custom(
+            Status::Conflict,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: BattleError::NotInPreview.message(),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    let (own, opponent) = (&game.players[side], &game.players[1 - side]);
+    Ok(status::Custom(
+        Status::Ok,
+        Json(GetTeamPreview {
+            trace_id,
+            game_status: game.state,
+            team: own.team.clone().unwrap_or_default(),
+            //Hidden until both teams are in, so nobody can pick a team against the other one.
+            opposing_species: if game.teams_submitted() {
+                opponent
+                    .team
+                    .iter()
+                    .flatten()
+                    .map(|set| set.species.clone())
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            bring: dex.formats.get(game.format).bring,
+            deadline,
+            lead_order: own.lead_order.clone(),
+            opponent_picked: opponent.lead_order.is_some(),
+        }),
+    ))
+}
+
+#[put("/games/<id>/preview", format = "json", data = "<request>")]
+pub(crate) async fn submit_lead_order(
+    id: &str,
+    request: Json<SubmitLeadOrderRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<SubmitLeadOrderResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!(
+        "{} | Received submit lead order request for id: {}",
+        trace_id,
+        id
+    );
+    let mut game = match db.get_current_game(id, dex, unix_time()).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let request = request.into_inner();
+    let Some(side) = game.side_of(&request.player_id) else {
+        log::error!("{} | Player is not part of game {}", trace_id, id);
+        return Err(status::Custom(
+            Status::Forbidden,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("You are not a player of this game."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    let events = match game.submit_lead_order(dex, side, request.lead_order, unix_time()) {
+        Ok(events) => events
+            .zip(game.battle.as_ref())
+            .map(|(events, battle)| battle.events_for(side, &events)),
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message());
+            return Err(status::Custom(
+                battle_error_status(&err),
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message(),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    match db.update_game(game).await {
+        Ok(_) => {
+            log::info!(
+                "{} | Submitted lead order for side {} of game {}",
+                trace_id,
+                side,
+                id
+            );
+            Ok(status::Custom(
+                Status::Ok,
+                Json(SubmitLeadOrder {
+                    trace_id,
+                    battle_started: events.is_some(),
+                    events: events.unwrap_or_default(),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[post("/games/<id>/actions", format = "json", data = "<request>")]
+pub(crate) async fn submit_action(
+    id: &str,
+    request: Json<SubmitActionRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<SubmitActionResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!(
+        "{} | Received submit action request for id: {}",
+        trace_id,
+        id
+    );
+    let mut game = match db.get_current_game(id, dex, unix_time()).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let request = request.into_inner();
+    let Some(side) = game.side_of(&request.player_id) else {
+        log::error!("{} | Player is not part of game {}", trace_id, id);
+        return Err(status::Custom(
+            Status::Forbidden,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("You are not a player of this game."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    let events = match game.submit_action(dex, side, request.position, request.action, unix_time())
+    {
+        Ok(events) => events
+            .zip(game.battle.as_ref())
+            .map(|(events, battle)| battle.events_for(side, &events)),
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message());
+            return Err(status::Custom(
+                battle_error_status(&err),
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message(),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    match db.update_game(game).await {
+        Ok(_) => {
+            log::info!(
+                "{} | Submitted action for side {} of game {}",
+                trace_id,
+                side,
+                id
+            );
+            Ok(status::Custom(
+                Status::Ok,
+                Json(SubmitAction {
+                    trace_id,
+                    turn_resolved: events.is_some(),
+                    events: events.unwrap_or_default(),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/games/<id>/battle?<player_id>")]
+pub(crate) async fn get_battle(
+    id: &str,
+    player_id: &str,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<GetBattleResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get battle request for id: {}", trace_id, id);
+    let game = match db.get_current_game(id, dex, unix_time()).await {
+        Ok(game) => game,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let Some(side) = game.side_of(player_id) else {
+        log::error!("{} | Player is not part of game {}", trace_id, id);
+        return Err(status::Custom(
+            Status::Forbidden,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("You are not a player of this game."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    match &game.battle {
+        Some(battle) => Ok(status::Custom(
+            Status::Ok,
+            Json(GetBattle {
+                trace_id,
+                game_status: game.state,
+                battle: battle.view_for(side),
+            }),
+        )),
+        None => {
+            log::info!("{} | Battle of game {} has not started", trace_id, id);
+            Err(status::Custom(
+                Status::Conflict,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: BattleError::NotStarted.message(),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api::requests::{
+        SaveTeamRequest, SubmitActionRequest, SubmitLeadOrderRequest, SubmitTeamRequest,
+    };
+    use crate::battle::{stats::Stats, team::CreatureSet, Action, BattleFormat};
+    use crate::{api::responses, *};
+    use rocket::{http::Status, local::asynchronous::Client};
+    use testcontainers_modules::{
+        surrealdb,
+        testcontainers::{runners::AsyncRunner, ImageExt},
+    };
+
+    const SURREALDB_VERSION: &str = "v2.0.4";
+
+    fn team(species: &str, move_id: &str) -> Vec<CreatureSet> {
+        vec![CreatureSet {
+            species: String::from(species),
+            nickname: None,
+            level: 50,
+            ability: None,
+            item: None,
+            moves: vec![String::from(move_id)],
+            investment: Stats::default(),
+            nature: None,
+        }]
+    }
+
+    //Creates a game through the API which is in the team preview and returns its id together with both player ids.
+    async fn create_previewed_game(client: &Client) -> (String, String, String) {
+        let created = client
+            .post(uri!(super::super::lobbies::create_game(_)))
+            .dispatch()
+            .await
+            .into_json::<responses::CreateGame>()
+            .await
+            .expect("Invalid response from server.");
+        let joined = client
+            .put(uri!(super::super::lobbies::join_game(
+                created.game_id.clone()
+            )))
+            .dispatch()
+            .await
+            .into_json::<responses::JoinGame>()
+            .await
+            .expect("Invalid response from server.");
+        for (player_id, species) in [
+            (created.player_id.clone(), "tidepup"),
+            (joined.player_id.clone(), "sproutle"),
+        ] {
+            let response = client
+                .put(uri!(super::submit_team(created.game_id.clone())))
+                .json(&SubmitTeamRequest {
+                    player_id,
+                    team: Some(team(species, "tackle")),
+                    team_id: None,
+                })
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+        (created.game_id, created.player_id, joined.player_id)
+    }
+
+    async fn create_started_game(client: &Client) -> (String, String, String) {
+        let (game_id, host, guest) = create_previewed_game(client).await;
+        for player_id in [host.clone(), guest.clone()] {
+            let response = client
+                .put(uri!(super::submit_lead_order(game_id.clone())))
+                .json(&SubmitLeadOrderRequest {
+                    player_id,
+                    lead_order: vec![0],
+                })
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+        (game_id, host, guest)
+    }
+
+    #[rocket::async_test]
+    async fn test_submitting_actions_resolves_the_turn() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let (game_id, host, guest) = create_started_game(&client).await;
+
+        let response = client
+            .post(uri!(super::submit_action(game_id.clone())))
+            .json(&SubmitActionRequest {
+                player_id: host,
+                position: 0,
+                action: Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::SubmitAction>()
+            .await
+            .expect("Invalid response from server.");
+        assert!(!response.turn_resolved);
+
+        let response = client
+            .post(uri!(super::submit_action(game_id)))
+            .json(&SubmitActionRequest {
+                player_id: guest,
+                position: 0,
+                action: Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::SubmitAction>()
+            .await
+            .expect("Invalid response from server.");
+        assert!(response.turn_resolved);
+        assert!(!response.events.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn test_simultaneous_actions_do_not_overwrite_each_other() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let (game_id, _, _) = create_started_game(&client).await;
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let dex = client.rocket().state::<Dex>().unwrap();
+        let action = Action::Move {
+            slot: 0,
+            target: None,
+        };
+
+        let mut first = db.get_game(&game_id).await.ok().unwrap();
+        let mut second = db.get_game(&game_id).await.ok().unwrap();
+        first.submit_action(dex, 0, 0, action, 0).unwrap();
+        second.submit_action(dex, 1, 0, action, 0).unwrap();
+
+        assert!(db.update_game(first).await.is_ok());
+        let conflict = db.update_game(second).await.err().unwrap();
+        assert_eq!(conflict.status_code, Status::Conflict);
+        let game = db.get_game(&game_id).await.ok().unwrap();
+        assert!(game.battle.unwrap().sides[1].pending_actions[0].is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_invalid_actions_are_rejected() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let (game_id, host, _) = create_started_game(&client).await;
+
+        let response = client
+            .post(uri!(super::submit_action(game_id.clone())))
+            .json(&SubmitActionRequest {
+                player_id: String::from("stranger"),
+                position: 0,
+                action: Action::Forfeit,
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .post(uri!(super::submit_action(game_id)))
+            .json(&SubmitActionRequest {
+                player_id: host,
+                position: 0,
+                action: Action::Move {
+                    slot: 3,
+                    target: None,
+                },
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[rocket::async_test]
+    async fn test_submitting_an_invalid_team() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let created = client
+            .post(uri!(super::super::lobbies::create_game(_)))
+            .dispatch()
+            .await
+            .into_json::<responses::CreateGame>()
+            .await
+            .expect("Invalid response from server.");
+
+        let response = client
+            .put(uri!(super::submit_team(created.game_id)))
+            .json(&SubmitTeamRequest {
+                player_id: created.player_id,
+                team: Some(team("missingno", "tackle")),
+                team_id: None,
+            })
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[rocket::async_test]
+    async fn test_submitting_a_saved_team() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        //The team is saved long before the game exists, by a player id which isn't part of any game.
+        let saved = client
+            .post(uri!(super::super::teams::create_team))
+            .json(&SaveTeamRequest {
+                player_id: String::from("ash"),
+                name: String::from("Rain"),
+                format: BattleFormat::Singles,
+                creatures: team("tidepup", "tackle"),
+            })
+            .dispatch()
+            .await
+            .into_json::<responses::CreateTeam>()
+            .await
+            .expect("Invalid response from server.");
+        let created = client
+            .post(uri!(super::super::lobbies::create_game(_)))
+            .dispatch()
+            .await
+            .into_json::<responses::CreateGame>()
+            .await
+            .expect("Invalid response from server.");
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let unknown = String::from("unknown");
+        let submit = |team, team_id: Option<&String>| SubmitTeamRequest {
+            player_id: created.player_id.clone(),
+            team,
+            team_id: team_id.cloned(),
+        };
+
+        for (request, expected) in [
+            (submit(None, Some(&unknown)), Status::NotFound),
+            (submit(None, None), Status::BadRequest),
+            (
+                submit(Some(team("tidepup", "tackle")), Some(&saved.team_id)),
+                Status::BadRequest,
+            ),
+            (submit(None, Some(&saved.team_id)), Status::Ok),
+        ] {
+            let response = client
+                .put(uri!(super::submit_team(created.game_id.clone())))
+                .json(&request)
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), expected);
+        }
+        let game = db.get_game(&created.game_id).await.ok().unwrap();
+        assert_eq!(game.players[0].team, Some(team("tidepup", "tackle")));
+    }
+
+    #[rocket::async_test]
+    async fn test_battle_view_hides_the_opponent() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let (game_id, host, _) = create_started_game(&client).await;
+
+        let response = client
+            .get(uri!(super::get_battle(game_id.clone(), host)))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetBattle>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.battle.side, 0);
+        assert_eq!(response.battle.own.team[0].species, "tidepup");
+        assert_eq!(response.battle.opponent.team[0].species, "sproutle");
+        assert!(response.battle.opponent.team[0].moves.is_empty());
+
+        let response = client
+            .get(uri!(super::get_battle(game_id, "stranger")))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn test_team_preview_only_shows_the_opposing_species() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let (game_id, host, guest) = create_previewed_game(&client).await;
+
+        let response = client
+            .get(uri!(super::get_team_preview(game_id.clone(), host.clone())))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetTeamPreview>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.team[0].species, "tidepup");
+        assert_eq!(response.opposing_species, vec!["sproutle"]);
+        assert_eq!(response.bring, 6);
+        assert!(!response.opponent_picked);
+
+        let response = client
+            .get(uri!(super::get_battle(game_id.clone(), host.clone())))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Conflict);
+        let response = client
+            .put(uri!(super::submit_lead_order(game_id.clone())))
+            .json(&SubmitLeadOrderRequest {
+                player_id: host,
+                lead_order: vec![1],
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let response = client
+            .get(uri!(super::get_team_preview(game_id, guest)))
+            .dispatch()
+            .await
+            .into_json::<responses::GetTeamPreview>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.opposing_species, vec!["tidepup"]);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    api::responses::{
+        types::{
+            ErrorResponse, GetMoveListResponse, GetMoveResponse, GetSpeciesListResponse,
+            GetSpeciesResponse, GetTypeChartResponse,
+        },
+        ErrorMessage, GetMove, GetMoveList, GetSpecies, GetSpeciesList, GetTypeChart,
+    },
+    dex::Dex,
+};
+use rocket::{http::Status, response::status, serde::json::Json, State};
+use uuid::Uuid;
+
+#[get("/species")]
+pub(crate) async fn get_species_list(dex: &State<Dex>) -> GetSpeciesListResponse {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get species list request", trace_id);
+    status::Custom(
+        Status::Ok,
+        Json(GetSpeciesList {
+            trace_id,
+            species: dex.species.all().cloned().collect(),
+        }),
+    )
+}
+
+#[get("/species/<id>")]
+pub(crate) async fn get_species(
+    id: &str,
+    dex: &State<Dex>,
+) -> Result<GetSpeciesResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get species request for id: {}", trace_id, id);
+    match dex.species.get(id) {
+        Some(species) => Ok(status::Custom(
+            Status::Ok,
+            Json(GetSpecies {
+                trace_id,
+                species: species.clone(),
+            }),
+        )),
+        None => {
+            log::error!("{} | Species with id {} does not exist", trace_id, id);
+            Err(status::Custom(
+                Status::NotFound,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: String::from("Couldn't find the species you're looking for."),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/moves")]
+pub(crate) async fn get_move_list(dex: &State<Dex>) -> GetMoveListResponse {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get move list request", trace_id);
+    status::Custom(
+        Status::Ok,
+        Json(GetMoveList {
+            trace_id,
+            moves: dex.moves.all().cloned().collect(),
+        }),
+    )
+}
+
+#[get("/moves/<id>")]
+pub(crate) async fn get_move(id: &str, dex: &State<Dex>) -> Result<GetMoveResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get move request for id: {}", trace_id, id);
+    match dex.moves.get(id) {
+        Some(found) => Ok(status::Custom(
+            Status::Ok,
+            Json(GetMove {
+                trace_id,
+                r#move: found.clone(),
+            }),
+        )),
+        None => {
+            log::error!("{} | Move with id {} does not exist", trace_id, id);
+            Err(status::Custom(
+                Status::NotFound,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: String::from("Couldn't find the move you're looking for."),
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/types/chart")]
+pub(crate) async fn get_type_chart(dex: &State<Dex>) -> GetTypeChartResponse {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get type chart request", trace_id);
+    status::Custom(
+        Status::Ok,
+        Json(GetTypeChart {
+            trace_id,
+            chart: dex.types.clone(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{api::responses, dex::Dex, DATA_DIRECTORY};
+    use rocket::{http::Status, local::asynchronous::Client};
+
+    async fn create_client() -> Client {
+        let rocket = rocket::build()
+            .manage(Dex::load(DATA_DIRECTORY).unwrap())
+            .mount(
+                "/",
+                routes![
+                    super::get_species_list,
+                    super::get_species,
+                    super::get_move_list,
+                    super::get_move,
+                    super::get_type_chart
+                ],
+            );
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_get_species_list() {
+        let client = create_client().await;
+
+        let response = client.get(uri!(super::get_species_list)).dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetSpeciesList>()
+            .await
+            .expect("Invalid response from server.");
+        assert!(!response.species.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn test_get_species() {
+        let client = create_client().await;
+
+        let response = client
+            .get(uri!(super::get_species("tidepup")))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetSpecies>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.species.id, "tidepup");
+    }
+
+    #[rocket::async_test]
+    async fn test_get_non_existent_species() {
+        let client = create_client().await;
+
+        let response = client
+            .get(uri!(super::get_species("missingno")))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_move() {
+        let client = create_client().await;
+
+        let response = client.get(uri!(super::get_move("surf"))).dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetMove>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.r#move.move_type, "water");
+    }
+
+    #[rocket::async_test]
+    async fn test_get_non_existent_move() {
+        let client = create_client().await;
+
+        let response = client
+            .get(uri!(super::get_move("splash-of-doom")))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[rocket::async_test]
+    async fn test_get_type_chart() {
+        let client = create_client().await;
+
+        let response = client.get(uri!(super::get_type_chart)).dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let response = response
+            .into_json::<responses::GetTypeChart>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.chart.multiplier("water", "fire"), 2.0);
+    }
+}
@@ -1,18 +1,33 @@
 use crate::{
-    api::responses::{
-        types::{
-            CancelGameResponse, CreateGameResponse, ErrorResponse, GetGameStatusResponse,
-            JoinGameResponse,
+    api::{
+        battles::unix_time,
+        responses::{
+            types::{
+                CancelGameResponse, CreateGameResponse, ErrorResponse, GetGameStatusResponse,
+                JoinGameResponse,
+            },
+            CancelGame, CreateGame, ErrorMessage, GetGameStatus, JoinGame,
         },
-        CancelGame, CreateGame, ErrorMessage, GetGameStatus, JoinGame,
     },
+    battle::BattleFormat,
     db::{entities::GameState, DbConnection},
+    dex::Dex,
 };
 use rocket::{http::Status, response::status, serde::json::Json, State};
 use uuid::Uuid;
 
-#[post("/games")]
+//Games are singles unless the format says otherwise.
+fn parse_format(format: Option<&str>) -> Option<BattleFormat> {
+    match format {
+        None | Some("singles") => Some(BattleFormat::Singles),
+        Some("doubles") => Some(BattleFormat::Doubles),
+        Some(_) => None,
+    }
+}
+
+#[post("/games?<format>")]
 pub(crate) async fn create_game(
+    format: Option<&str>,
     db: &State<DbConnection>,
 ) -> Result<CreateGameResponse, ErrorResponse> {
     let trace_id = Uuid::new_v4();
@@ -21,8 +36,22 @@ pub(crate) async fn create_game(
     https://github.com/estk/log4rs/pull/362 should make it more clean in the future.
      */
     log::info!("{} | Received create game request", trace_id.to_string());
-    match db.create_game().await {
-        Ok(game_id) => {
+    let Some(format) = parse_format(format) else {
+        log::error!("{} | Unknown battle format", trace_id.to_string());
+        return Err(status::Custom(
+            Status::BadRequest,
+            Json(ErrorMessage {
+                trace_id,
+                error_message: String::from("The format has to be either singles or doubles."),
+                error_code: None,
+                errors: Vec::new(),
+            }),
+        ));
+    };
+    match db.create_game(format).await {
+        Ok(game) => {
+            let game_id = game.id.id.to_string();
+            let player_id = game.players[0].id.clone();
             log::info!(
                 "{} | Created game with id: {}",
                 trace_id.to_string(),
@@ -30,7 +59,11 @@ pub(crate) async fn create_game(
             );
             Ok(status::Custom(
                 Status::Created,
-                Json(CreateGame { trace_id, game_id }),
+                Json(CreateGame {
+                    trace_id,
+                    game_id,
+                    player_id,
+                }),
             ))
         }
         Err(err) => {
@@ -41,6 +74,7 @@ pub(crate) async fn create_game(
                     trace_id,
                     error_message: err.message,
                     error_code: None,
+                    errors: Vec::new(),
                 }),
             ))
         }
@@ -51,6 +85,7 @@ pub(crate) async fn create_game(
 pub(crate) async fn join_game(
     id: &str,
     db: &State<DbConnection>,
+    dex: &State<Dex>,
 ) -> Result<JoinGameResponse, ErrorResponse> {
     let trace_id = Uuid::new_v4();
     log::info!(
@@ -58,7 +93,7 @@ pub(crate) async fn join_game(
         trace_id.to_string(),
         id
     );
-    match db.get_game(id).await {
+    match db.get_current_game(id, dex, unix_time()).await {
         Ok(mut game) => {
             if game.state != GameState::Pending {
                 log::error!(
@@ -72,10 +107,11 @@ pub(crate) async fn join_game(
                         trace_id,
                         error_message: String::from("The game is already active or finished."),
                         error_code: None,
+                        errors: Vec::new(),
                     }),
                 ));
             }
-            game.state = GameState::Ongoing;
+            let player_id = game.join(dex, unix_time());
             match db.update_game(game).await {
                 Ok(_) => {
                     log::info!("{} | Joined game with id {}", trace_id.to_string(), id);
@@ -84,6 +120,7 @@ pub(crate) async fn join_game(
                         Json(JoinGame {
                             trace_id,
                             message: String::from("Joined the game."),
+                            player_id,
                         }),
                     ))
                 }
@@ -95,6 +132,7 @@ pub(crate) async fn join_game(
                             trace_id,
                             error_message: err.message,
                             error_code: None,
+                            errors: Vec::new(),
                         }),
                     ))
                 }
@@ -108,6 +146,7 @@ pub(crate) async fn join_game(
                     trace_id,
                     error_message: err.message,
                     error_code: None,
+                    errors: Vec::new(),
                 }),
             ))
         }
@@ -118,6 +157,7 @@ pub(crate) async fn join_game(
 pub(crate) async fn get_game_state(
     id: &str,
     db: &State<DbConnection>,
+    dex: &State<Dex>,
 ) -> Result<GetGameStatusResponse, ErrorResponse> {
     let trace_id = Uuid::new_v4();
     log::info!(
@@ -125,7 +165,7 @@ pub(crate) async fn get_game_state(
         trace_id.to_string(),
         id
     );
-    match db.get_game(id).await {
+    match db.get_current_game(id, dex, unix_time()).await {
         Ok(game) => {
             log::info!("{} | Received game status {:?}", trace_id, game.state);
             Ok(status::Custom(
@@ -144,6 +184,7 @@ pub(crate) async fn get_game_state(
                     trace_id,
                     error_message: err.message,
                     error_code: None,
+                    errors: Vec::new(),
                 }),
             ))
         }
@@ -154,6 +195,7 @@ pub(crate) async fn get_game_state(
 pub(crate) async fn cancel_game(
     id: &str,
     db: &State<DbConnection>,
+    dex: &State<Dex>,
 ) -> Result<CancelGameResponse, ErrorResponse> {
     let trace_id = Uuid::new_v4();
     log::info!(
@@ -161,9 +203,9 @@ pub(crate) async fn cancel_game(
         trace_id.to_string(),
         id
     );
-    match db.get_game(id).await {
+    match db.get_current_game(id, dex, unix_time()).await {
         Ok(mut game) => match game.state {
-            GameState::Pending | GameState::Ongoing => {
+            GameState::Pending | GameState::TeamPreview | GameState::Ongoing => {
                 game.state = GameState::Cancelled;
                 match db.update_game(game).await {
                     Ok(_) => {
@@ -178,6 +220,7 @@ pub(crate) async fn cancel_game(
                                 trace_id,
                                 error_message: err.message,
                                 error_code: None,
+                                errors: Vec::new(),
                             }),
                         ))
                     }
@@ -191,6 +234,7 @@ pub(crate) async fn cancel_game(
                         trace_id,
                         error_message: String::from("Game can not be cancelled. Either the game is already cancelled or it is already finished."),
                         error_code: None,
+                        errors: Vec::new(),
                     }),
                 ))
             }
@@ -203,6 +247,7 @@ pub(crate) async fn cancel_game(
                     trace_id,
                     error_message: err.message,
                     error_code: None,
+                    errors: Vec::new(),
                 }),
             ))
         }
@@ -210,6 +255,11 @@ pub(crate) async fn cancel_game(
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::useless_conversion,
+    clippy::field_reassign_with_default,
+    clippy::clone_on_copy
+)]
 mod test {
     use crate::db::entities::Game;
     use crate::{api::responses, db::entities::GameState, *};
@@ -268,11 +318,47 @@ mod test {
             .await
             .unwrap();
 
-        let response = client.post(uri!(super::create_game)).dispatch().await;
+        let response = client.post(uri!(super::create_game(_))).dispatch().await;
 
         assert_eq!(response.status(), Status::Created);
     }
 
+    #[rocket::async_test]
+    async fn test_creating_games_with_a_format() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "localhost:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+
+        let doubles = client
+            .post(uri!(super::create_game(Some("doubles"))))
+            .dispatch()
+            .await;
+        let unknown = client
+            .post(uri!(super::create_game(Some("triples"))))
+            .dispatch()
+            .await;
+
+        assert_eq!(doubles.status(), Status::Created);
+        assert_eq!(unknown.status(), Status::BadRequest);
+    }
+
     #[rocket::async_test]
     async fn test_joining_a_game() {
         let db_instance = surrealdb::SurrealDb::default()
@@ -599,4 +685,67 @@ mod test {
             assert_eq!(response.status(), Status::Conflict);
         }
     }
+
+    #[rocket::async_test]
+    async fn test_overdue_team_previews_get_saved_by_every_request() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let mut games = Vec::new();
+        for _ in 0..2 {
+            let mut overdue = Game {
+                state: GameState::TeamPreview,
+                team_deadline: Some(0),
+                ..Game::default()
+            };
+            overdue.add_player();
+            overdue.add_player();
+            let created_game: Game = db
+                .conn
+                .create("games")
+                .content(overdue)
+                .await
+                .expect("Creating game failed.")
+                .expect("");
+            games.push(created_game.id.id.to_string());
+        }
+
+        let response = client
+            .get(uri!(super::get_game_state(games[0].clone())))
+            .dispatch()
+            .await
+            .into_json::<responses::GetGameStatus>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(response.game_status, GameState::Cancelled);
+        let cancelled = client
+            .put(uri!(super::cancel_game(games[1].clone())))
+            .dispatch()
+            .await;
+        assert_eq!(cancelled.status(), Status::Conflict);
+
+        for id in games {
+            let game = db.get_game(&id).await.ok().unwrap();
+            assert_eq!(game.state, GameState::Cancelled);
+            assert_eq!(game.version, 1);
+        }
+    }
 }
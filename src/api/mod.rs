@@ -1,9 +1,37 @@
-use crate::api::lobbies::*;
+use crate::api::{admin::*, battles::*, dex::*, lobbies::*, teams::*};
 use rocket::Route;
 
+mod admin;
+mod battles;
+mod dex;
 mod lobbies;
+pub mod requests;
 pub mod responses;
+mod teams;
 
 pub fn get_routes() -> Vec<Route> {
-    routes![create_game, join_game, get_game_state, cancel_game]
+    routes![
+        create_game,
+        join_game,
+        get_game_state,
+        cancel_game,
+        get_species_list,
+        get_species,
+        get_move_list,
+        get_move,
+        get_type_chart,
+        resimulate_game,
+        submit_team,
+        get_team_preview,
+        submit_lead_order,
+        submit_action,
+        get_battle,
+        create_team,
+        list_teams,
+        get_team,
+        update_team,
+        delete_team,
+        import_team,
+        export_team
+    ]
 }
@@ -0,0 +1,47 @@
+use crate::battle::{team::CreatureSet, Action, BattleFormat};
+use rocket::serde::{Deserialize, Serialize};
+
+//Either the team itself or the id of a team the player saved.
+#[derive(Serialize, Deserialize)]
+pub struct SubmitTeamRequest {
+    pub(crate) player_id: String,
+    #[serde(default)]
+    pub(crate) team: Option<Vec<CreatureSet>>,
+    #[serde(default)]
+    pub(crate) team_id: Option<String>,
+}
+
+//Indices into the submitted team, the first creatures lead.
+#[derive(Serialize, Deserialize)]
+pub struct SubmitLeadOrderRequest {
+    pub(crate) player_id: String,
+    pub(crate) lead_order: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitActionRequest {
+    pub(crate) player_id: String,
+    //Only needed in doubles, singles always act from the first position.
+    #[serde(default)]
+    pub(crate) position: usize,
+    pub(crate) action: Action,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveTeamRequest {
+    pub(crate) player_id: String,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) format: BattleFormat,
+    pub(crate) creatures: Vec<CreatureSet>,
+}
+
+//The team comes as plain text, like players share it.
+#[derive(Serialize, Deserialize)]
+pub struct ImportTeamRequest {
+    pub(crate) player_id: String,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) format: BattleFormat,
+    pub(crate) paste: String,
+}
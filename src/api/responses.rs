@@ -1,4 +1,10 @@
-use crate::db::entities::GameState;
+use crate::{
+    battle::{
+        team::CreatureSet, validation::TeamError, view::BattleView, BattleEvent, BattleFormat,
+    },
+    db::entities::{GameState, Team},
+    dex::{moves::Move, species::Species, types::TypeChart},
+};
 use rocket::serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -6,12 +12,14 @@ use uuid::Uuid;
 pub struct CreateGame {
     pub(crate) trace_id: Uuid,
     pub(crate) game_id: String,
+    pub(crate) player_id: String,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct JoinGame {
     pub(crate) trace_id: Uuid,
     pub(crate) message: String,
+    pub(crate) player_id: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -30,10 +38,153 @@ pub struct ErrorMessage {
     pub(crate) trace_id: Uuid,
     pub(crate) error_message: String,
     pub(crate) error_code: Option<u8>,
+    //Every rule a submitted team breaks, the error_code above is the one of the first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) errors: Vec<TeamError>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSpeciesList {
+    pub(crate) trace_id: Uuid,
+    pub(crate) species: Vec<Species>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSpecies {
+    pub(crate) trace_id: Uuid,
+    pub(crate) species: Species,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetMoveList {
+    pub(crate) trace_id: Uuid,
+    pub(crate) moves: Vec<Move>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetMove {
+    pub(crate) trace_id: Uuid,
+    pub(crate) r#move: Move,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetTypeChart {
+    pub(crate) trace_id: Uuid,
+    pub(crate) chart: TypeChart,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ResimulateGame {
+    pub(crate) trace_id: Uuid,
+    pub(crate) seed: u32,
+    pub(crate) matches: bool,
+    pub(crate) recorded_winner: Option<usize>,
+    pub(crate) resimulated_winner: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitTeam {
+    pub(crate) trace_id: Uuid,
+    pub(crate) preview_started: bool,
+}
+
+//Players only get to see the species of the other team, not how they are built.
+#[derive(Serialize, Deserialize)]
+pub struct GetTeamPreview {
+    pub(crate) trace_id: Uuid,
+    pub(crate) game_status: GameState,
+    pub(crate) team: Vec<CreatureSet>,
+    pub(crate) opposing_species: Vec<String>,
+    pub(crate) bring: usize,
+    pub(crate) deadline: i64,
+    pub(crate) lead_order: Option<Vec<usize>>,
+    pub(crate) opponent_picked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitLeadOrder {
+    pub(crate) trace_id: Uuid,
+    pub(crate) battle_started: bool,
+    pub(crate) events: Vec<BattleEvent>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmitAction {
+    pub(crate) trace_id: Uuid,
+    pub(crate) turn_resolved: bool,
+    pub(crate) events: Vec<BattleEvent>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetBattle {
+    pub(crate) trace_id: Uuid,
+    pub(crate) game_status: GameState,
+    pub(crate) battle: BattleView,
+}
+
+//A saved team with its id flattened into a string, like the ids of games.
+#[derive(Serialize, Deserialize)]
+pub struct SavedTeam {
+    pub(crate) team_id: String,
+    pub(crate) player_id: String,
+    pub(crate) name: String,
+    pub(crate) format: BattleFormat,
+    pub(crate) creatures: Vec<CreatureSet>,
+}
+
+impl From<Team> for SavedTeam {
+    fn from(team: Team) -> Self {
+        SavedTeam {
+            team_id: team.id.id.to_string(),
+            player_id: team.player_id,
+            name: team.name,
+            format: team.format,
+            creatures: team.creatures,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateTeam {
+    pub(crate) trace_id: Uuid,
+    pub(crate) team_id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetTeam {
+    pub(crate) trace_id: Uuid,
+    pub(crate) team: SavedTeam,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetTeamList {
+    pub(crate) trace_id: Uuid,
+    pub(crate) teams: Vec<SavedTeam>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateTeam {
+    pub(crate) trace_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteTeam {
+    pub(crate) trace_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ExportTeam {
+    pub(crate) trace_id: Uuid,
+    pub(crate) paste: String,
 }
 
 pub mod types {
-    use crate::api::responses::{CancelGame, CreateGame, ErrorMessage, GetGameStatus, JoinGame};
+    use crate::api::responses::{
+        CancelGame, CreateGame, CreateTeam, DeleteTeam, ErrorMessage, ExportTeam, GetBattle,
+        GetGameStatus, GetMove, GetMoveList, GetSpecies, GetSpeciesList, GetTeam, GetTeamList,
+        GetTeamPreview, GetTypeChart, JoinGame, ResimulateGame, SubmitAction, SubmitLeadOrder,
+        SubmitTeam, UpdateTeam,
+    };
     use rocket::response::status;
     use rocket::serde::json::Json;
 
@@ -43,4 +194,21 @@ pub mod types {
     pub type JoinGameResponse = status::Custom<Json<JoinGame>>;
     pub type CancelGameResponse = status::Custom<Json<CancelGame>>;
     pub type GetGameStatusResponse = status::Custom<Json<GetGameStatus>>;
+    pub type GetSpeciesListResponse = status::Custom<Json<GetSpeciesList>>;
+    pub type GetSpeciesResponse = status::Custom<Json<GetSpecies>>;
+    pub type GetMoveListResponse = status::Custom<Json<GetMoveList>>;
+    pub type GetMoveResponse = status::Custom<Json<GetMove>>;
+    pub type GetTypeChartResponse = status::Custom<Json<GetTypeChart>>;
+    pub type ResimulateGameResponse = status::Custom<Json<ResimulateGame>>;
+    pub type SubmitTeamResponse = status::Custom<Json<SubmitTeam>>;
+    pub type SubmitActionResponse = status::Custom<Json<SubmitAction>>;
+    pub type GetBattleResponse = status::Custom<Json<GetBattle>>;
+    pub type GetTeamPreviewResponse = status::Custom<Json<GetTeamPreview>>;
+    pub type SubmitLeadOrderResponse = status::Custom<Json<SubmitLeadOrder>>;
+    pub type CreateTeamResponse = status::Custom<Json<CreateTeam>>;
+    pub type GetTeamResponse = status::Custom<Json<GetTeam>>;
+    pub type GetTeamListResponse = status::Custom<Json<GetTeamList>>;
+    pub type UpdateTeamResponse = status::Custom<Json<UpdateTeam>>;
+    pub type DeleteTeamResponse = status::Custom<Json<DeleteTeam>>;
+    pub type ExportTeamResponse = status::Custom<Json<ExportTeam>>;
 }
@@ -0,0 +1,610 @@
+use crate::{
+    api::{
+        requests::{ImportTeamRequest, SaveTeamRequest},
+        responses::{
+            types::{
+                CreateTeamResponse, DeleteTeamResponse, ErrorResponse, ExportTeamResponse,
+                GetTeamListResponse, GetTeamResponse, UpdateTeamResponse,
+            },
+            CreateTeam, DeleteTeam, ErrorMessage, ExportTeam, GetTeam, GetTeamList, UpdateTeam,
+        },
+    },
+    battle::{
+        paste::{export_paste, parse_paste},
+        team::CreatureSet,
+        validation::{validate_team, TeamErrorCode},
+        BattleFormat,
+    },
+    db::{entities::Team, DbConnection},
+    dex::Dex,
+};
+use rocket::{http::Status, response::status, serde::json::Json, State};
+use uuid::Uuid;
+
+//Saved teams have to follow the rules of their format, so they can be submitted to a game as they are.
+fn check_team(
+    trace_id: Uuid,
+    dex: &Dex,
+    format: BattleFormat,
+    creatures: &[CreatureSet],
+) -> Result<(), ErrorResponse> {
+    match validate_team(dex, dex.formats.get(format), creatures) {
+        Ok(_) => Ok(()),
+        Err(errors) => {
+            log::error!("{} | The team breaks {} rules", trace_id, errors.len());
+            Err(status::Custom(
+                Status::UnprocessableEntity,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: String::from("The team breaks the rules of its format."),
+                    error_code: errors.first().map(|error| error.error_code),
+                    errors,
+                }),
+            ))
+        }
+    }
+}
+
+//Only the player who saved a team gets to change or delete it.
+fn check_owner(trace_id: Uuid, team: &Team, player_id: &str) -> Result<(), ErrorResponse> {
+    if team.player_id == player_id {
+        return Ok(());
+    }
+    log::error!(
+        "{} | Player is not the owner of team {}",
+        trace_id,
+        team.id.id
+    );
+    Err(status::Custom(
+        Status::Forbidden,
+        Json(ErrorMessage {
+            trace_id,
+            error_message: String::from("You are not the owner of this team."),
+            error_code: None,
+            errors: Vec::new(),
+        }),
+    ))
+}
+
+#[post("/teams", format = "json", data = "<request>")]
+pub(crate) async fn create_team(
+    request: Json<SaveTeamRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<CreateTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received create team request", trace_id);
+    let request = request.into_inner();
+    check_team(trace_id, dex, request.format, &request.creatures)?;
+    let team = Team::new(
+        &request.player_id,
+        &request.name,
+        request.format,
+        request.creatures,
+    );
+    match db.create_team(team).await {
+        Ok(team) => {
+            let team_id = team.id.id.to_string();
+            log::info!("{} | Created team with id: {}", trace_id, team_id);
+            Ok(status::Custom(
+                Status::Created,
+                Json(CreateTeam { trace_id, team_id }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/teams?<player_id>")]
+pub(crate) async fn list_teams(
+    player_id: &str,
+    db: &State<DbConnection>,
+) -> Result<GetTeamListResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received list teams request", trace_id);
+    match db.list_teams(player_id).await {
+        Ok(teams) => {
+            log::info!("{} | Found {} teams", trace_id, teams.len());
+            Ok(status::Custom(
+                Status::Ok,
+                Json(GetTeamList {
+                    trace_id,
+                    teams: teams.into_iter().map(Into::into).collect(),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/teams/<id>")]
+pub(crate) async fn get_team(
+    id: &str,
+    db: &State<DbConnection>,
+) -> Result<GetTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received get team request for id: {}", trace_id, id);
+    match db.get_team(id).await {
+        Ok(team) => {
+            log::info!("{} | Found team with id {}", trace_id, id);
+            Ok(status::Custom(
+                Status::Ok,
+                Json(GetTeam {
+                    trace_id,
+                    team: team.into(),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[put("/teams/<id>", format = "json", data = "<request>")]
+pub(crate) async fn update_team(
+    id: &str,
+    request: Json<SaveTeamRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<UpdateTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received update team request for id: {}", trace_id, id);
+    let mut team = match db.get_team(id).await {
+        Ok(team) => team,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    let request = request.into_inner();
+    check_owner(trace_id, &team, &request.player_id)?;
+    check_team(trace_id, dex, request.format, &request.creatures)?;
+    team.name = request.name;
+    team.format = request.format;
+    team.creatures = request.creatures;
+    match db.update_team(team).await {
+        Ok(_) => {
+            log::info!("{} | Updated team with id {}", trace_id, id);
+            Ok(status::Custom(Status::Ok, Json(UpdateTeam { trace_id })))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[delete("/teams/<id>?<player_id>")]
+pub(crate) async fn delete_team(
+    id: &str,
+    player_id: &str,
+    db: &State<DbConnection>,
+) -> Result<DeleteTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received delete team request for id: {}", trace_id, id);
+    let team = match db.get_team(id).await {
+        Ok(team) => team,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            return Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    check_owner(trace_id, &team, player_id)?;
+    match db.delete_team(id).await {
+        Ok(_) => {
+            log::info!("{} | Deleted team with id {}", trace_id, id);
+            Ok(status::Custom(Status::Ok, Json(DeleteTeam { trace_id })))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[post("/teams/import", format = "json", data = "<request>")]
+pub(crate) async fn import_team(
+    request: Json<ImportTeamRequest>,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<CreateTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received import team request", trace_id);
+    let request = request.into_inner();
+    let creatures = match parse_paste(&request.paste) {
+        Ok(creatures) => creatures,
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message());
+            return Err(status::Custom(
+                Status::UnprocessableEntity,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message(),
+                    error_code: Some(TeamErrorCode::InvalidPaste as u8),
+                    errors: Vec::new(),
+                }),
+            ));
+        }
+    };
+    check_team(trace_id, dex, request.format, &creatures)?;
+    let team = Team::new(&request.player_id, &request.name, request.format, creatures);
+    match db.create_team(team).await {
+        Ok(team) => {
+            let team_id = team.id.id.to_string();
+            log::info!("{} | Imported team with id: {}", trace_id, team_id);
+            Ok(status::Custom(
+                Status::Created,
+                Json(CreateTeam { trace_id, team_id }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[get("/teams/<id>/export")]
+pub(crate) async fn export_team(
+    id: &str,
+    db: &State<DbConnection>,
+    dex: &State<Dex>,
+) -> Result<ExportTeamResponse, ErrorResponse> {
+    let trace_id = Uuid::new_v4();
+    log::info!("{} | Received export team request for id: {}", trace_id, id);
+    match db.get_team(id).await {
+        Ok(team) => {
+            log::info!("{} | Exported team with id {}", trace_id, id);
+            Ok(status::Custom(
+                Status::Ok,
+                Json(ExportTeam {
+                    trace_id,
+                    paste: export_paste(dex, &team.creatures),
+                }),
+            ))
+        }
+        Err(err) => {
+            log::error!("{} | {}", trace_id, err.message);
+            Err(status::Custom(
+                err.status_code,
+                Json(ErrorMessage {
+                    trace_id,
+                    error_message: err.message,
+                    error_code: None,
+                    errors: Vec::new(),
+                }),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api::requests::{ImportTeamRequest, SaveTeamRequest};
+    use crate::battle::{
+        stats::{Nature, Stats},
+        team::CreatureSet,
+        BattleFormat,
+    };
+    use crate::db::entities::Team;
+    use crate::dex::moves::BoostableStat;
+    use crate::{api::responses, *};
+    use rocket::{http::Status, local::asynchronous::Client};
+    use testcontainers_modules::{
+        surrealdb,
+        testcontainers::{runners::AsyncRunner, ImageExt},
+    };
+
+    const SURREALDB_VERSION: &str = "v2.0.4";
+
+    fn request(player_id: &str, name: &str, species: &str) -> SaveTeamRequest {
+        SaveTeamRequest {
+            player_id: String::from(player_id),
+            name: String::from(name),
+            format: BattleFormat::Singles,
+            creatures: vec![CreatureSet {
+                species: String::from(species),
+                nickname: None,
+                level: 50,
+                ability: None,
+                item: None,
+                moves: vec![String::from("tackle")],
+                investment: Stats::default(),
+                nature: None,
+            }],
+        }
+    }
+
+    #[rocket::async_test]
+    async fn test_saved_teams_can_be_listed_and_changed() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+
+        let response = client
+            .post(uri!(super::create_team))
+            .json(&request("player", "Rain", "tidepup"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Created);
+        let team_id = response
+            .into_json::<responses::CreateTeam>()
+            .await
+            .expect("Invalid response from server.")
+            .team_id;
+
+        let response = client
+            .put(uri!(super::update_team(team_id.clone())))
+            .json(&request("player", "Sun", "cindercub"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let teams = client
+            .get(uri!(super::list_teams("player")))
+            .dispatch()
+            .await
+            .into_json::<responses::GetTeamList>()
+            .await
+            .expect("Invalid response from server.")
+            .teams;
+        assert_eq!(teams.len(), 1);
+        assert_eq!(teams[0].team_id, team_id);
+        assert_eq!(teams[0].name, "Sun");
+        assert_eq!(teams[0].creatures[0].species, "cindercub");
+
+        let response = client
+            .delete(uri!(super::delete_team(team_id.clone(), "player")))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = client.get(uri!(super::get_team(team_id))).dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[rocket::async_test]
+    async fn test_saved_teams_keep_investment_and_nature() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let db = client.rocket().state::<DbConnection>().unwrap();
+        let mut creatures = request("player", "Rain", "tidepup").creatures;
+        creatures[0].investment.attack = 252;
+        creatures[0].investment.speed = 252;
+        creatures[0].nature = Some(Nature {
+            raised: BoostableStat::Speed,
+            lowered: BoostableStat::SpecialAttack,
+        });
+        //Investment and nature are optional, the second creature leaves both out.
+        creatures.push(request("player", "Rain", "sproutle").creatures.remove(0));
+        let team = Team::new("player", "Rain", BattleFormat::Singles, creatures);
+
+        let created = db.create_team(team.clone()).await.ok().unwrap();
+        let loaded = db.get_team(&created.id.id.to_string()).await.ok().unwrap();
+
+        assert_eq!(created, team);
+        assert_eq!(loaded, team);
+        assert_eq!(loaded.creatures[1].investment, Stats::default());
+        assert_eq!(loaded.creatures[1].nature, None);
+    }
+
+    #[rocket::async_test]
+    async fn test_teams_are_checked_before_saving() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+
+        let response = client
+            .post(uri!(super::create_team))
+            .json(&request("player", "Glitch", "missingno"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+
+        let team_id = client
+            .post(uri!(super::create_team))
+            .json(&request("player", "Rain", "tidepup"))
+            .dispatch()
+            .await
+            .into_json::<responses::CreateTeam>()
+            .await
+            .expect("Invalid response from server.")
+            .team_id;
+        let response = client
+            .put(uri!(super::update_team(team_id.clone())))
+            .json(&request("stranger", "Stolen", "tidepup"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+        let response = client
+            .delete(uri!(super::delete_team(team_id, "stranger")))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn test_teams_can_be_imported_and_exported_as_text() {
+        let db_instance = surrealdb::SurrealDb::default()
+            .with_tag(SURREALDB_VERSION)
+            .start()
+            .await
+            .expect("Something went wrong. Do you have a container runtime installed?");
+        let rocket = rocket::build();
+        let config = Config {
+            db_url: format!(
+                "127.0.0.1:{}",
+                db_instance
+                    .get_host_port_ipv4(surrealdb::SURREALDB_PORT)
+                    .await
+                    .unwrap()
+            ),
+            username: String::from("root"),
+            password: String::from("root"),
+        };
+        let client = Client::tracked(build_the_rocket(rocket, config).await)
+            .await
+            .unwrap();
+        let paste = "Tidepup @ Oran Berry\nLevel: 50\n- Tackle\n- Water Gun\n";
+
+        let response = client
+            .post(uri!(super::import_team))
+            .json(&ImportTeamRequest {
+                player_id: String::from("player"),
+                name: String::from("Rain"),
+                format: BattleFormat::Singles,
+                paste: String::from("Tidepup\nLevel: fifty"),
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let response = client
+            .post(uri!(super::import_team))
+            .json(&ImportTeamRequest {
+                player_id: String::from("player"),
+                name: String::from("Rain"),
+                format: BattleFormat::Singles,
+                paste: String::from(paste),
+            })
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Created);
+        let team_id = response
+            .into_json::<responses::CreateTeam>()
+            .await
+            .expect("Invalid response from server.")
+            .team_id;
+
+        let exported = client
+            .get(uri!(super::export_team(team_id)))
+            .dispatch()
+            .await
+            .into_json::<responses::ExportTeam>()
+            .await
+            .expect("Invalid response from server.");
+        assert_eq!(exported.paste, paste);
+    }
+}
@@ -0,0 +1,475 @@
+use crate::battle::{
+    hooks::{parse_handler, EffectHandler, Hit, HookContext},
+    Battle, BattleEvent, Position,
+};
+use crate::dex::{
+    abilities::Ability,
+    fields::{TerrainKind, WeatherKind},
+    moves::{BoostableStat, EffectTarget, MoveCategory, StatusKind},
+    statuses::Fraction,
+    DexError,
+};
+use rocket::serde::{de::DeserializeOwned, Deserialize};
+use std::collections::BTreeMap;
+
+pub struct LoadedAbility {
+    pub(crate) data: Ability,
+    handler: Box<dyn EffectHandler>,
+}
+
+pub struct AbilityRegistry {
+    abilities: BTreeMap<String, LoadedAbility>,
+}
+
+impl LoadedAbility {
+    pub fn handler(&self) -> &dyn EffectHandler {
+        self.handler.as_ref()
+    }
+}
+
+impl AbilityRegistry {
+    pub fn new(abilities: Vec<Ability>) -> Result<Self, DexError> {
+        let mut registry = BTreeMap::new();
+        for entry in abilities {
+            if registry.contains_key(&entry.id) {
+                return Err(DexError {
+                    message: format!("Ability {} is defined more than once.", entry.id),
+                });
+            }
+            let handler = build_handler(&entry)?;
+            registry.insert(
+                entry.id.clone(),
+                LoadedAbility {
+                    data: entry,
+                    handler,
+                },
+            );
+        }
+        Ok(AbilityRegistry {
+            abilities: registry,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LoadedAbility> {
+        self.abilities.get(id)
+    }
+}
+
+fn build_handler(ability: &Ability) -> Result<Box<dyn EffectHandler>, DexError> {
+    match ability.handler.as_str() {
+        "None" => Ok(Box::new(NoEffect)),
+        "PinchBoost" => parse::<PinchBoost>(ability),
+        "TypeImmunity" => parse::<TypeImmunity>(ability),
+        "RetaliateDamage" => parse::<RetaliateDamage>(ability),
+        "RetaliateStatus" => parse::<RetaliateStatus>(ability),
+        "StatChangeOnEntry" => parse::<StatChangeOnEntry>(ability),
+        "ResistSuperEffective" => parse::<ResistSuperEffective>(ability),
+        "StatChangeAtEndOfTurn" => parse::<StatChangeAtEndOfTurn>(ability),
+        "StatusImmunity" => parse::<StatusImmunity>(ability),
+        "SetWeather" => parse::<SetWeather>(ability),
+        "SetTerrain" => parse::<SetTerrain>(ability),
+        unknown => Err(DexError {
+            message: format!(
+                "Ability {} uses the unknown handler {}.",
+                ability.id, unknown
+            ),
+        }),
+    }
+}
+
+fn parse<T: EffectHandler + DeserializeOwned + 'static>(
+    ability: &Ability,
+) -> Result<Box<dyn EffectHandler>, DexError> {
+    parse_handler::<T>(&format!("Ability {}", ability.id), &ability.parameters)
+}
+
+struct NoEffect;
+
+impl EffectHandler for NoEffect {}
+
+//Powers up moves of one type while the creature is low on health.
+#[derive(Deserialize)]
+struct PinchBoost {
+    move_type: String,
+    multiplier: f32,
+    threshold: Fraction,
+}
+
+impl EffectHandler for PinchBoost {
+    fn before_damage(&self, battle: &Battle, context: &mut HookContext, hit: &mut Hit) {
+        let creature = battle.creature(context.position);
+        if hit.attacker == context.position
+            && hit.used_move.move_type == self.move_type
+            && creature.current_hp <= self.threshold.of(creature.stats.hp)
+        {
+            hit.modifier *= self.multiplier;
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TypeImmunity {
+    move_type: String,
+}
+
+impl EffectHandler for TypeImmunity {
+    fn before_damage(&self, _battle: &Battle, context: &mut HookContext, hit: &mut Hit) {
+        if hit.defender == context.position && hit.used_move.move_type == self.move_type {
+            hit.effectiveness = 0.0;
+            context.announce();
+        }
+    }
+}
+
+fn hit_by_physical_move(context: &HookContext, hit: &Hit) -> bool {
+    hit.defender == context.position
+        && hit.attacker != context.position
+        && hit.used_move.category == MoveCategory::Physical
+        && hit.dealt > 0
+}
+
+#[derive(Deserialize)]
+struct RetaliateDamage {
+    fraction: Fraction,
+}
+
+impl EffectHandler for RetaliateDamage {
+    fn after_damage(&self, battle: &mut Battle, context: &mut HookContext, hit: &Hit) {
+        let attacker = battle.creature_mut(hit.attacker);
+        if !hit_by_physical_move(context, hit) || attacker.is_fainted() {
+            return;
+        }
+        context.announce();
+        let dealt = attacker.take_damage(self.fraction.of(attacker.stats.hp));
+        context.events.push(BattleEvent::Damaged {
+            position: hit.attacker,
+            amount: dealt,
+            remaining_hp: attacker.current_hp,
+        });
+        battle.check_fainted(hit.attacker, context.events);
+    }
+}
+
+#[derive(Deserialize)]
+struct RetaliateStatus {
+    status: StatusKind,
+    chance: u8,
+}
+
+impl EffectHandler for RetaliateStatus {
+    fn after_damage(&self, battle: &mut Battle, context: &mut HookContext, hit: &Hit) {
+        if !hit_by_physical_move(context, hit) || !context.rng.percent(self.chance) {
+            return;
+        }
+        let mut events = Vec::new();
+        if battle.apply_status(
+            context.dex,
+            context.rng,
+            hit.attacker,
+            self.status,
+            &mut events,
+        ) {
+            context.announce();
+            context.events.append(&mut events);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StatChangeOnEntry {
+    stat: BoostableStat,
+    stages: i8,
+    target: EffectTarget,
+}
+
+impl EffectHandler for StatChangeOnEntry {
+    fn on_switch_in(&self, battle: &mut Battle, context: &mut HookContext) {
+        //Lowering the stats of the foes hits every foe on the field.
+        let targets: Vec<Position> = match self.target {
+            EffectTarget::User => vec![context.position],
+            EffectTarget::Target => battle.foes(context.position),
+        }
+        .into_iter()
+        .filter(|target| !battle.creature(*target).is_fainted())
+        .collect();
+        if targets.is_empty() {
+            return;
+        }
+        context.announce();
+        for target in targets {
+            battle.change_stage(target, self.stat, self.stages, context.events);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ResistSuperEffective {
+    multiplier: f32,
+}
+
+impl EffectHandler for ResistSuperEffective {
+    fn before_damage(&self, _battle: &Battle, context: &mut HookContext, hit: &mut Hit) {
+        if hit.defender == context.position && hit.effectiveness > 1.0 {
+            hit.modifier *= self.multiplier;
+            context.announce();
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StatChangeAtEndOfTurn {
+    stat: BoostableStat,
+    stages: i8,
+}
+
+impl EffectHandler for StatChangeAtEndOfTurn {
+    fn on_end_of_turn(&self, battle: &mut Battle, context: &mut HookContext) {
+        let mut events = Vec::new();
+        if battle.change_stage(context.position, self.stat, self.stages, &mut events) {
+            context.announce();
+            context.events.append(&mut events);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StatusImmunity {
+    statuses: Vec<StatusKind>,
+}
+
+impl EffectHandler for StatusImmunity {
+    fn allows_status(&self, _battle: &Battle, context: &mut HookContext, kind: StatusKind) -> bool {
+        if self.statuses.contains(&kind) {
+            context.announce();
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(Deserialize)]
+struct SetWeather {
+    weather: WeatherKind,
+}
+
+impl EffectHandler for SetWeather {
+    fn on_switch_in(&self, battle: &mut Battle, context: &mut HookContext) {
+        let mut events = Vec::new();
+        if battle.set_weather(context.dex, self.weather, &mut events) {
+            context.announce();
+            context.events.append(&mut events);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SetTerrain {
+    terrain: TerrainKind,
+}
+
+impl EffectHandler for SetTerrain {
+    fn on_switch_in(&self, battle: &mut Battle, context: &mut HookContext) {
+        let mut events = Vec::new();
+        if battle.set_terrain(context.dex, self.terrain, &mut events) {
+            context.announce();
+            context.events.append(&mut events);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        abilities::AbilityRegistry,
+        creature::{BattleCreature, BattleMove},
+        rng::BattleRng,
+        stats::Stats,
+        Action, Battle, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::{moves::StatusKind, Dex};
+    use crate::DATA_DIRECTORY;
+
+    fn creature(species: &str, ability: &str, move_id: &str) -> BattleCreature {
+        let mut creature = BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        );
+        creature.ability = String::from(ability);
+        creature
+    }
+
+    fn play_turn(battle: &mut Battle, dex: &Dex) -> Vec<BattleEvent> {
+        battle
+            .submit_action(
+                dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unknown_handlers_are_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut ability = dex.abilities.get("hovering").unwrap().data.clone();
+        ability.handler = String::from("Levitation");
+
+        assert!(AbilityRegistry::new(vec![ability]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut ability = dex.abilities.get("hovering").unwrap().data.clone();
+        ability.handler = String::from("PinchBoost");
+
+        assert!(AbilityRegistry::new(vec![ability]).is_err());
+    }
+
+    #[test]
+    fn test_switch_in_hooks_run_when_the_battle_starts() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("frostfang", "intimidate-glare", "tackle")],
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+            ],
+            1,
+        );
+
+        let events = battle.start(&dex);
+
+        assert_eq!(
+            events[0],
+            BattleEvent::AbilityTriggered {
+                position: Position { side: 0, index: 0 },
+                ability: String::from("intimidate-glare")
+            }
+        );
+        assert_eq!(battle.sides[1].creature_at(0).stages.attack, -1);
+        assert!(battle.sides[0].creature_at(0).ability_revealed);
+        assert!(!battle.sides[1].creature_at(0).ability_revealed);
+    }
+
+    #[test]
+    fn test_type_immunity_prevents_damage() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", "sturdy-shell", "earthquake")],
+                vec![creature("gloomwisp", "hovering", "fairy-wind")],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::AbilityTriggered {
+            position: Position { side: 1, index: 0 },
+            ability: String::from("hovering")
+        }));
+        assert_eq!(battle.sides[1].creature_at(0).current_hp, 160);
+    }
+
+    #[test]
+    fn test_retaliation_hurts_physical_attackers() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+                vec![creature("thornwyrm", "rough-hide", "sharpen-claws")],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::Damaged {
+            position: Position { side: 0, index: 0 },
+            amount: 20,
+            remaining_hp: 140
+        }));
+    }
+
+    #[test]
+    fn test_status_immunity_blocks_the_status() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+                vec![creature("mindmoth", "wakeful", "tackle")],
+            ],
+            1,
+        );
+        let mut events = Vec::new();
+
+        let applied = battle.apply_status(
+            &dex,
+            &mut BattleRng::new(1),
+            Position { side: 1, index: 0 },
+            StatusKind::Sleep,
+            &mut events,
+        );
+
+        assert!(!applied);
+        assert_eq!(
+            events,
+            vec![BattleEvent::AbilityTriggered {
+                position: Position { side: 1, index: 0 },
+                ability: String::from("wakeful")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_end_of_turn_hooks_raise_stats() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("plumbird", "tailwind-soul", "guard-up")],
+                vec![creature("tidepup", "torrent-heart", "guard-up")],
+            ],
+            1,
+        );
+
+        play_turn(&mut battle, &dex);
+        play_turn(&mut battle, &dex);
+
+        assert_eq!(battle.sides[0].creature_at(0).stages.speed, 2);
+        assert_eq!(battle.sides[1].creature_at(0).stages.speed, 0);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::battle::{rng::BattleRng, volatile::StatStages};
+use crate::dex::{mechanics::CriticalHitRules, moves::BoostableStat};
+
+//Everything deciding whether a move connects. The stages of the attacker's accuracy and the
+//defender's evasion cancel each other out before they scale the accuracy of the move.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct AccuracyCheck {
+    //Moves without an accuracy never miss.
+    pub(crate) accuracy: Option<u8>,
+    pub(crate) accuracy_stage: i8,
+    pub(crate) evasion_stage: i8,
+}
+
+impl AccuracyCheck {
+    //The chance to hit in percent, None if the move can't miss at all.
+    pub fn hit_chance(&self) -> Option<u32> {
+        let accuracy = self.accuracy?;
+        let stage = self.accuracy_stage - self.evasion_stage;
+        Some((accuracy as f32 * StatStages::multiplier(BoostableStat::Accuracy, stage)) as u32)
+    }
+
+    //Moves which can't miss don't advance the generator.
+    pub fn roll(&self, rng: &mut BattleRng) -> bool {
+        match self.hit_chance() {
+            Some(chance) => rng.chance(chance, 100),
+            None => true,
+        }
+    }
+}
+
+//Every stage raises the chance of a critical hit to the next entry of the rules.
+pub fn roll_critical_hit(rules: &CriticalHitRules, stage: u8, rng: &mut BattleRng) -> bool {
+    rng.chance(1, rules.chance(stage))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::accuracy::{roll_critical_hit, AccuracyCheck};
+    use crate::battle::rng::BattleRng;
+    use crate::dex::mechanics::CriticalHitRules;
+
+    fn check(accuracy: Option<u8>, accuracy_stage: i8, evasion_stage: i8) -> AccuracyCheck {
+        AccuracyCheck {
+            accuracy,
+            accuracy_stage,
+            evasion_stage,
+        }
+    }
+
+    fn rules() -> CriticalHitRules {
+        CriticalHitRules {
+            multiplier: 1.5,
+            chances: vec![24, 8, 2, 1],
+        }
+    }
+
+    //Finds a seed whose first roll between 1 and the denominator is the given value.
+    fn seed_rolling(value: u32, denominator: u32) -> u64 {
+        (0..)
+            .find(|seed| BattleRng::new(*seed).range(1, denominator) == value)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stages_scale_the_accuracy() {
+        assert_eq!(check(Some(90), 0, 0).hit_chance(), Some(90));
+        assert_eq!(check(Some(90), 3, 0).hit_chance(), Some(180));
+        assert_eq!(check(Some(90), 0, 3).hit_chance(), Some(45));
+        assert_eq!(check(Some(90), 2, 2).hit_chance(), Some(90));
+    }
+
+    #[test]
+    fn test_combined_stages_are_capped() {
+        assert_eq!(
+            check(Some(60), 6, -6).hit_chance(),
+            check(Some(60), 6, 0).hit_chance()
+        );
+        assert_eq!(check(Some(60), -6, 6).hit_chance(), Some(20));
+    }
+
+    #[test]
+    fn test_moves_without_accuracy_never_miss() {
+        let mut rng = BattleRng::new(5);
+
+        assert_eq!(check(None, -6, 6).hit_chance(), None);
+        assert!(check(None, -6, 6).roll(&mut rng));
+        assert_eq!(rng.next_u64(), BattleRng::new(5).next_u64());
+    }
+
+    #[test]
+    fn test_forced_miss_and_hit() {
+        let seed = seed_rolling(80, 100);
+
+        assert!(!check(Some(70), 0, 0).roll(&mut BattleRng::new(seed)));
+        assert!(check(Some(80), 0, 0).roll(&mut BattleRng::new(seed)));
+        assert!(check(Some(70), 1, 0).roll(&mut BattleRng::new(seed)));
+    }
+
+    #[test]
+    fn test_forced_critical_hit() {
+        let seed = seed_rolling(1, 24);
+        let missed = seed_rolling(2, 24);
+
+        assert!(roll_critical_hit(&rules(), 0, &mut BattleRng::new(seed)));
+        assert!(!roll_critical_hit(&rules(), 0, &mut BattleRng::new(missed)));
+    }
+
+    #[test]
+    fn test_maximum_stage_always_lands_a_critical_hit() {
+        let mut rng = BattleRng::new(9);
+
+        for stage in 3..10 {
+            assert!(roll_critical_hit(&rules(), stage, &mut rng));
+        }
+    }
+
+    #[test]
+    fn test_higher_stages_land_more_critical_hits() {
+        let count = |stage: u8| {
+            let mut rng = BattleRng::new(77);
+            (0..1000)
+                .filter(|_| roll_critical_hit(&rules(), stage, &mut rng))
+                .count()
+        };
+
+        assert!(count(0) < count(1));
+        assert!(count(1) < count(2));
+    }
+}
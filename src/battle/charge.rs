@@ -0,0 +1,242 @@
+use crate::battle::{
+    rng::BattleRng, volatile::VolatileKind, Action, Battle, BattleEvent, BattlePhase, Position,
+};
+use crate::dex::moves::Move;
+
+impl Battle {
+    //Returns whether the move only got charged, in which case it hits with the same action next turn.
+    pub(super) fn charge_move(
+        &mut self,
+        rng: &mut BattleRng,
+        position: Position,
+        used_move: &Move,
+        action: Action,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let Some(charge) = used_move.charge else {
+            return false;
+        };
+        if self.has_volatile(position, VolatileKind::Charging) {
+            self.end_charge(position, events);
+            return false;
+        }
+        events.push(BattleEvent::ChargingMove {
+            position,
+            move_id: used_move.id.clone(),
+        });
+        self.add_volatile(rng, position, VolatileKind::Charging, events);
+        if charge.semi_invulnerable {
+            self.add_volatile(rng, position, VolatileKind::SemiInvulnerable, events);
+        }
+        self.creature_mut(position).forced_action = Some(action);
+        true
+    }
+
+    //Also called when the creature can't act on the second turn, which loses the charge.
+    pub(super) fn end_charge(&mut self, position: Position, events: &mut Vec<BattleEvent>) {
+        self.remove_volatile(position, VolatileKind::Charging, events);
+        self.remove_volatile(position, VolatileKind::SemiInvulnerable, events);
+    }
+
+    pub(super) fn start_recharge(
+        &mut self,
+        rng: &mut BattleRng,
+        position: Position,
+        action: Action,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        if self.add_volatile(rng, position, VolatileKind::Recharging, events) {
+            self.creature_mut(position).forced_action = Some(action);
+        }
+    }
+
+    //Returns whether the creature spent its turn recharging.
+    pub(super) fn recharge(&mut self, position: Position, events: &mut Vec<BattleEvent>) -> bool {
+        if !self.has_volatile(position, VolatileKind::Recharging) {
+            return false;
+        }
+        events.push(BattleEvent::PreventedByVolatile {
+            position,
+            volatile: VolatileKind::Recharging,
+        });
+        self.remove_volatile(position, VolatileKind::Recharging, events);
+        true
+    }
+
+    //Creatures which are charging or recharging don't get to pick an action, it gets submitted
+    //for them as soon as the next turn starts.
+    pub(super) fn submit_forced_actions(&mut self) {
+        if self.phase != BattlePhase::WaitingForActions {
+            return;
+        }
+        for side in self.sides.iter_mut() {
+            for index in 0..side.active.len() {
+                let creature = side.creature_at(index);
+                if !creature.is_fainted() && creature.forced_action.is_some() {
+                    side.pending_actions[index] = creature.forced_action;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        stats::Stats,
+        volatile::VolatileKind,
+        Action, Battle, BattleError, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::Dex;
+    use crate::DATA_DIRECTORY;
+
+    const FIRST: Position = Position { side: 0, index: 0 };
+    const SECOND: Position = Position { side: 1, index: 0 };
+
+    fn creature(species: &str, speed: u16, move_id: &str) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 300,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 10,
+                revealed: false,
+            }],
+        )
+    }
+
+    fn attack() -> Action {
+        Action::Move {
+            slot: 0,
+            target: None,
+        }
+    }
+
+    fn turns(events: &[BattleEvent]) -> usize {
+        events
+            .iter()
+            .filter(|event| matches!(event, BattleEvent::TurnOrder { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_charged_moves_hit_on_the_second_turn() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("sproutle", 100, "solar-beam")],
+                vec![creature("tidepup", 50, "sharpen-claws")],
+            ],
+            1,
+        );
+
+        battle.submit_action(&dex, 0, 0, attack()).unwrap();
+        let first = battle.submit_action(&dex, 1, 0, attack()).unwrap().unwrap();
+
+        assert!(first.contains(&BattleEvent::ChargingMove {
+            position: FIRST,
+            move_id: String::from("solar-beam")
+        }));
+        assert_eq!(battle.creature(SECOND).current_hp, 300);
+        assert!(matches!(
+            battle.submit_action(&dex, 0, 0, attack()),
+            Err(BattleError::LockedIn)
+        ));
+        assert_eq!(battle.view_for(0).own.locked_in, vec![true]);
+
+        let second = battle.submit_action(&dex, 1, 0, attack()).unwrap().unwrap();
+
+        assert!(second.contains(&BattleEvent::UsedMove {
+            position: FIRST,
+            move_id: String::from("solar-beam")
+        }));
+        assert!(battle.creature(SECOND).current_hp < 300);
+        assert_eq!(battle.creature(FIRST).moves[0].pp, 9);
+        assert!(battle.creature(FIRST).volatiles.is_empty());
+        assert_eq!(battle.view_for(0).own.locked_in, vec![false]);
+    }
+
+    #[test]
+    fn test_semi_invulnerable_creatures_cant_be_hit() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("plumbird", 100, "fly")],
+                vec![creature("tidepup", 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle.submit_action(&dex, 0, 0, attack()).unwrap();
+        let events = battle.submit_action(&dex, 1, 0, attack()).unwrap().unwrap();
+
+        assert!(battle.has_volatile(FIRST, VolatileKind::SemiInvulnerable));
+        assert!(events.contains(&BattleEvent::Missed { position: FIRST }));
+        assert_eq!(battle.creature(FIRST).current_hp, 300);
+    }
+
+    #[test]
+    fn test_recharging_skips_the_next_turn() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("frostfang", 100, "hyper-beam")],
+                vec![creature("tidepup", 50, "sharpen-claws")],
+            ],
+            1,
+        );
+
+        battle.submit_action(&dex, 0, 0, attack()).unwrap();
+        battle.submit_action(&dex, 1, 0, attack()).unwrap();
+        assert!(battle.has_volatile(FIRST, VolatileKind::Recharging));
+        let events = battle.submit_action(&dex, 1, 0, attack()).unwrap().unwrap();
+
+        assert!(events.contains(&BattleEvent::PreventedByVolatile {
+            position: FIRST,
+            volatile: VolatileKind::Recharging
+        }));
+        assert_eq!(battle.creature(FIRST).moves[0].pp, 9);
+        assert!(battle
+            .submit_action(&dex, 0, 0, attack())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_turns_resolve_on_their_own_when_everyone_is_locked_in() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let start = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("frostfang", 100, "hyper-beam")],
+                vec![creature("pyrolisk", 50, "hyper-beam")],
+            ],
+            1,
+        );
+        let mut battle = start.clone();
+
+        battle.submit_action(&dex, 0, 0, attack()).unwrap();
+        let events = battle.submit_action(&dex, 1, 0, attack()).unwrap().unwrap();
+
+        assert_eq!(turns(&events), 2);
+        assert_eq!(battle.turn, 3);
+        assert!(battle.creature(FIRST).volatiles.is_empty());
+        assert_eq!(battle.history.len(), 2);
+        assert_eq!(
+            Battle::replay(&start, &battle.history, &dex).unwrap(),
+            battle
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    battle::{
+        hooks::{EffectHandler, EffectSource},
+        stats::{Nature, Stats},
+        status::Status,
+        volatile::{StatStages, Volatile},
+        Action,
+    },
+    dex::{
+        moves::{BoostableStat, Move},
+        species::Species,
+        Dex,
+    },
+};
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BattleMove {
+    pub(crate) id: String,
+    pub(crate) pp: u8,
+    //Whether the opponent has seen this move being used.
+    pub(crate) revealed: bool,
+}
+
+impl From<&Move> for BattleMove {
+    fn from(value: &Move) -> Self {
+        BattleMove {
+            id: value.id.clone(),
+            pp: value.pp,
+            revealed: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BattleCreature {
+    pub(crate) species: String,
+    pub(crate) level: u8,
+    pub(crate) stats: Stats,
+    pub(crate) current_hp: u16,
+    pub(crate) moves: Vec<BattleMove>,
+    pub(crate) status: Option<Status>,
+    pub(crate) stages: StatStages,
+    pub(crate) volatiles: Vec<Volatile>,
+    pub(crate) ability: String,
+    //Whether the opponent has seen the ability do something.
+    pub(crate) ability_revealed: bool,
+    //Consumable items are gone once used.
+    pub(crate) item: Option<String>,
+    pub(crate) item_revealed: bool,
+    //Set by choice items, only the move in this slot can be picked until the creature switches out.
+    pub(crate) locked_move: Option<usize>,
+    //Set while charging or recharging, the action gets submitted automatically on the next turn.
+    #[serde(default)]
+    pub(crate) forced_action: Option<Action>,
+    //Whether the opponent has seen this creature on the field.
+    pub(crate) revealed: bool,
+}
+
+impl BattleCreature {
+    pub fn new(species: &str, level: u8, stats: Stats, moves: Vec<BattleMove>) -> Self {
+        BattleCreature {
+            species: String::from(species),
+            level,
+            stats,
+            current_hp: stats.hp,
+            moves,
+            status: None,
+            stages: StatStages::default(),
+            volatiles: Vec::new(),
+            ability: String::new(),
+            ability_revealed: false,
+            item: None,
+            item_revealed: false,
+            locked_move: None,
+            forced_action: None,
+            revealed: false,
+        }
+    }
+
+    pub fn from_species(
+        species: &Species,
+        level: u8,
+        investment: &Stats,
+        nature: Option<Nature>,
+        ability: &str,
+        moves: &[&Move],
+    ) -> Self {
+        let mut creature = Self::new(
+            &species.id,
+            level,
+            Stats::at_level(&species.base_stats, level, investment, nature),
+            moves.iter().map(|known| BattleMove::from(*known)).collect(),
+        );
+        creature.ability = String::from(ability);
+        creature
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.current_hp == 0
+    }
+
+    pub fn hp_percent(&self) -> u8 {
+        self.percent_of(self.current_hp)
+    }
+
+    //Rounded up, so a creature which is still standing never shows 0%.
+    pub fn percent_of(&self, hp: u16) -> u8 {
+        let max_hp = self.stats.hp.max(1) as u32;
+        (hp as u32 * 100).div_ceil(max_hp) as u8
+    }
+
+    pub fn effective_stat(&self, dex: &Dex, stat: BoostableStat) -> u16 {
+        let mut value =
+            self.stats.get(stat) as f32 * StatStages::multiplier(stat, self.stages.get(stat));
+        if let Some(status) = self.status {
+            value *= dex.statuses.get(status.kind).modifier(stat);
+        }
+        for (_, _, handler) in self.handlers(dex) {
+            value *= handler.stat_multiplier(stat);
+        }
+        (value as u16).max(1)
+    }
+
+    //Everything on the creature which reacts to battle events, the ability always goes first.
+    pub fn handlers<'d>(
+        &self,
+        dex: &'d Dex,
+    ) -> Vec<(EffectSource, &'d str, &'d dyn EffectHandler)> {
+        let ability = dex.abilities.get(&self.ability).map(|ability| {
+            (
+                EffectSource::Ability,
+                ability.data.id.as_str(),
+                ability.handler(),
+            )
+        });
+        let item = self
+            .item
+            .as_ref()
+            .and_then(|item| dex.items.get(item))
+            .map(|item| (EffectSource::Item, item.data.id.as_str(), item.handler()));
+        ability.into_iter().chain(item).collect()
+    }
+
+    pub fn take_damage(&mut self, amount: u16) -> u16 {
+        let dealt = amount.min(self.current_hp);
+        self.current_hp -= dealt;
+        dealt
+    }
+
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        let healed = amount.min(self.stats.hp - self.current_hp);
+        self.current_hp += healed;
+        healed
+    }
+
+    //Called when the creature leaves the field, nothing temporary survives a switch.
+    pub fn reset_volatiles(&mut self) {
+        self.stages = StatStages::default();
+        self.volatiles.clear();
+        self.locked_move = None;
+        self.forced_action = None;
+    }
+}
@@ -0,0 +1,274 @@
+use crate::battle::rng::BattleRng;
+use rocket::serde::{Deserialize, Serialize};
+
+pub const STAB_MULTIPLIER: f32 = 1.5;
+pub const MIN_ROLL: u8 = 85;
+pub const MAX_ROLL: u8 = 100;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct DamageRequest {
+    pub(crate) level: u8,
+    pub(crate) power: u16,
+    pub(crate) attack: u16,
+    pub(crate) defense: u16,
+    pub(crate) stab: bool,
+    pub(crate) effectiveness: f32,
+    //Everything else which scales the damage, like abilities and items.
+    pub(crate) modifier: f32,
+    //Only applies if the hit turns out to be a critical one.
+    pub(crate) critical_multiplier: f32,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct DamageRolls {
+    pub(crate) critical: bool,
+    pub(crate) roll: u8,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Damage {
+    pub(crate) amount: u16,
+    pub(crate) critical: bool,
+}
+
+//Every damage dealt in a battle goes through this function. The critical hit is always rolled
+//before the damage roll, so the sequence of random numbers stays stable.
+pub fn calculate_damage(request: &DamageRequest, critical: bool, rng: &mut BattleRng) -> Damage {
+    let roll = rng.range(MIN_ROLL as u32, MAX_ROLL as u32) as u8;
+    calculate_damage_with_rolls(request, DamageRolls { critical, roll })
+}
+
+pub fn calculate_damage_with_rolls(request: &DamageRequest, rolls: DamageRolls) -> Damage {
+    if request.power == 0 || request.effectiveness == 0.0 || request.modifier == 0.0 {
+        return Damage {
+            amount: 0,
+            critical: false,
+        };
+    }
+
+    let level_factor = 2 * request.level as u64 / 5 + 2;
+    let base = level_factor * request.power as u64 * request.attack as u64
+        / (request.defense as u64).max(1)
+        / 50
+        + 2;
+
+    let mut damage = base as f64;
+    if rolls.critical {
+        damage = (damage * request.critical_multiplier as f64).floor();
+    }
+    damage = (damage * rolls.roll as f64 / 100.0).floor();
+    if request.stab {
+        damage = (damage * STAB_MULTIPLIER as f64).floor();
+    }
+    damage = (damage * request.effectiveness as f64).floor();
+    damage = (damage * request.modifier as f64).floor();
+
+    Damage {
+        amount: damage.clamp(1.0, u16::MAX as f64) as u16,
+        critical: rolls.critical,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::damage::*;
+
+    fn request() -> DamageRequest {
+        DamageRequest {
+            level: 50,
+            power: 80,
+            attack: 120,
+            defense: 100,
+            stab: false,
+            effectiveness: 1.0,
+            modifier: 1.0,
+            critical_multiplier: 1.5,
+        }
+    }
+
+    fn rolls(critical: bool, roll: u8) -> DamageRolls {
+        DamageRolls { critical, roll }
+    }
+
+    #[test]
+    fn test_maximum_roll() {
+        //22 * 80 * 120 / 100 / 50 + 2 = 44
+        let damage = calculate_damage_with_rolls(&request(), rolls(false, 100));
+
+        assert_eq!(damage.amount, 44);
+        assert!(!damage.critical);
+    }
+
+    #[test]
+    fn test_minimum_roll() {
+        let damage = calculate_damage_with_rolls(&request(), rolls(false, 85));
+
+        assert_eq!(damage.amount, 37);
+    }
+
+    #[test]
+    fn test_every_roll_is_between_minimum_and_maximum() {
+        let mut previous = 0;
+        for roll in MIN_ROLL..=MAX_ROLL {
+            let damage = calculate_damage_with_rolls(&request(), rolls(false, roll)).amount;
+            assert!((37..=44).contains(&damage));
+            assert!(damage >= previous);
+            previous = damage;
+        }
+    }
+
+    #[test]
+    fn test_stab() {
+        let mut stab = request();
+        stab.stab = true;
+
+        assert_eq!(
+            calculate_damage_with_rolls(&stab, rolls(false, 100)).amount,
+            66
+        );
+    }
+
+    #[test]
+    fn test_critical_hit() {
+        let damage = calculate_damage_with_rolls(&request(), rolls(true, 100));
+
+        assert_eq!(damage.amount, 66);
+        assert!(damage.critical);
+    }
+
+    #[test]
+    fn test_type_effectiveness() {
+        let expected = [(4.0, 176), (2.0, 88), (0.5, 22), (0.25, 11)];
+        for (effectiveness, amount) in expected {
+            let mut typed = request();
+            typed.effectiveness = effectiveness;
+            assert_eq!(
+                calculate_damage_with_rolls(&typed, rolls(false, 100)).amount,
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn test_all_modifiers_combined() {
+        let mut combined = request();
+        combined.stab = true;
+        combined.effectiveness = 2.0;
+
+        //44 -> 66 (crit) -> 56 (roll) -> 84 (stab) -> 168 (effectiveness)
+        assert_eq!(
+            calculate_damage_with_rolls(&combined, rolls(true, 85)).amount,
+            168
+        );
+    }
+
+    #[test]
+    fn test_immunity_deals_no_damage() {
+        let mut immune = request();
+        immune.effectiveness = 0.0;
+
+        let damage = calculate_damage_with_rolls(&immune, rolls(true, 100));
+
+        assert_eq!(damage.amount, 0);
+        assert!(!damage.critical);
+    }
+
+    #[test]
+    fn test_zero_power_deals_no_damage() {
+        let mut powerless = request();
+        powerless.power = 0;
+
+        assert_eq!(
+            calculate_damage_with_rolls(&powerless, rolls(false, 100)).amount,
+            0
+        );
+    }
+
+    #[test]
+    fn test_minimum_damage_is_one() {
+        let weak = DamageRequest {
+            level: 1,
+            power: 10,
+            attack: 5,
+            defense: 500,
+            stab: false,
+            effectiveness: 0.25,
+            modifier: 1.0,
+            critical_multiplier: 1.5,
+        };
+
+        assert_eq!(
+            calculate_damage_with_rolls(&weak, rolls(false, 85)).amount,
+            1
+        );
+    }
+
+    #[test]
+    fn test_damage_is_capped() {
+        let huge = DamageRequest {
+            level: 100,
+            power: u16::MAX,
+            attack: u16::MAX,
+            defense: 1,
+            stab: true,
+            effectiveness: 4.0,
+            modifier: 1.0,
+            critical_multiplier: 1.5,
+        };
+
+        assert_eq!(
+            calculate_damage_with_rolls(&huge, rolls(true, 100)).amount,
+            u16::MAX
+        );
+    }
+
+    #[test]
+    fn test_modifier_applies_last() {
+        let mut boosted = request();
+        boosted.modifier = 1.5;
+
+        assert_eq!(
+            calculate_damage_with_rolls(&boosted, rolls(false, 100)).amount,
+            66
+        );
+        boosted.modifier = 0.0;
+        assert_eq!(
+            calculate_damage_with_rolls(&boosted, rolls(false, 100)).amount,
+            0
+        );
+    }
+
+    #[test]
+    fn test_zero_defense_does_not_panic() {
+        let mut no_defense = request();
+        no_defense.defense = 0;
+
+        assert!(calculate_damage_with_rolls(&no_defense, rolls(false, 100)).amount > 0);
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_damage() {
+        for seed in 0..50 {
+            let first = calculate_damage(&request(), false, &mut BattleRng::new(seed));
+            let second = calculate_damage(&request(), false, &mut BattleRng::new(seed));
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_seeded_damage_stays_in_range() {
+        let mut rng = BattleRng::new(1234);
+        let mut saw_critical = false;
+        for _ in 0..1000 {
+            let critical = rng.chance(1, 24);
+            let damage = calculate_damage(&request(), critical, &mut rng);
+            if damage.critical {
+                saw_critical = true;
+                assert!((56..=66).contains(&damage.amount));
+            } else {
+                assert!((37..=44).contains(&damage.amount));
+            }
+        }
+        assert!(saw_critical);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::battle::{Battle, BattleEvent, Position};
+use crate::dex::{
+    fields::{TerrainKind, WeatherKind},
+    moves::{Move, StatusKind},
+    Dex,
+};
+use rocket::serde::{Deserialize, Serialize};
+
+//Conditions affecting both sides at once. Setting a new weather or terrain replaces the old one.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct Field {
+    pub(crate) weather: Option<Weather>,
+    pub(crate) terrain: Option<Terrain>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Weather {
+    pub(crate) kind: WeatherKind,
+    pub(crate) turns_left: u8,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Terrain {
+    pub(crate) kind: TerrainKind,
+    pub(crate) turns_left: u8,
+}
+
+impl Battle {
+    //Fails if the same weather is already active, a different one gets replaced.
+    pub(super) fn set_weather(
+        &mut self,
+        dex: &Dex,
+        kind: WeatherKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        if self
+            .field
+            .weather
+            .is_some_and(|weather| weather.kind == kind)
+        {
+            return false;
+        }
+        self.field.weather = Some(Weather {
+            kind,
+            turns_left: dex.fields.weather(kind).duration,
+        });
+        events.push(BattleEvent::WeatherStarted { weather: kind });
+        true
+    }
+
+    pub(super) fn set_terrain(
+        &mut self,
+        dex: &Dex,
+        kind: TerrainKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        if self
+            .field
+            .terrain
+            .is_some_and(|terrain| terrain.kind == kind)
+        {
+            return false;
+        }
+        self.field.terrain = Some(Terrain {
+            kind,
+            turns_left: dex.fields.terrain(kind).duration,
+        });
+        events.push(BattleEvent::TerrainStarted { terrain: kind });
+        true
+    }
+
+    pub(super) fn field_power_modifier(&self, dex: &Dex, move_type: &str) -> f32 {
+        let weather = self.field.weather.map_or(1.0, |weather| {
+            dex.fields.weather(weather.kind).power_modifier(move_type)
+        });
+        let terrain = self.field.terrain.map_or(1.0, |terrain| {
+            dex.fields.terrain(terrain.kind).power_modifier(move_type)
+        });
+        weather * terrain
+    }
+
+    //Returns the terrain if it prevents the status, so the caller can report it.
+    pub(super) fn terrain_blocking_status(
+        &self,
+        dex: &Dex,
+        kind: StatusKind,
+    ) -> Option<TerrainKind> {
+        self.field
+            .terrain
+            .map(|terrain| terrain.kind)
+            .filter(|terrain| {
+                dex.fields
+                    .terrain(*terrain)
+                    .blocked_statuses
+                    .contains(&kind)
+            })
+    }
+
+    pub(super) fn terrain_blocking_priority(
+        &self,
+        dex: &Dex,
+        used_move: &Move,
+    ) -> Option<TerrainKind> {
+        self.field
+            .terrain
+            .map(|terrain| terrain.kind)
+            .filter(|terrain| {
+                used_move.priority > 0 && dex.fields.terrain(*terrain).blocks_priority
+            })
+    }
+
+    //Chip damage from the weather and healing from the terrain for a single creature.
+    pub(super) fn field_effects(
+        &mut self,
+        dex: &Dex,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let weather = self.field.weather;
+        let terrain = self.field.terrain;
+        let creature = self.creature_mut(position);
+        if creature.is_fainted() {
+            return;
+        }
+        let types = dex
+            .species
+            .get(&creature.species)
+            .map(|species| species.types.as_slice())
+            .unwrap_or_default();
+        if let Some(weather) = weather {
+            let condition = dex.fields.weather(weather.kind);
+            if let Some(damage) = condition.end_of_turn_damage {
+                if !types
+                    .iter()
+                    .any(|type_id| condition.immune_types.contains(type_id))
+                {
+                    let amount = creature.take_damage(damage.of(creature.stats.hp));
+                    events.push(BattleEvent::HurtByWeather {
+                        position,
+                        weather: weather.kind,
+                        amount,
+                        remaining_hp: creature.current_hp,
+                    });
+                }
+            }
+        }
+        if let Some(terrain) = terrain {
+            let condition = dex.fields.terrain(terrain.kind);
+            if let Some(heal) = condition.end_of_turn_heal {
+                if !creature.is_fainted() && creature.current_hp < creature.stats.hp {
+                    let amount = creature.heal(heal.of(creature.stats.hp));
+                    events.push(BattleEvent::Healed {
+                        position,
+                        amount,
+                        current_hp: creature.current_hp,
+                    });
+                }
+            }
+        }
+        self.check_fainted(position, events);
+    }
+
+    //Called once per turn after every other end of turn effect.
+    pub(super) fn field_countdown(&mut self, events: &mut Vec<BattleEvent>) {
+        if let Some(weather) = &mut self.field.weather {
+            weather.turns_left = weather.turns_left.saturating_sub(1);
+            if weather.turns_left == 0 {
+                events.push(BattleEvent::WeatherEnded {
+                    weather: weather.kind,
+                });
+                self.field.weather = None;
+            }
+        }
+        if let Some(terrain) = &mut self.field.terrain {
+            terrain.turns_left = terrain.turns_left.saturating_sub(1);
+            if terrain.turns_left == 0 {
+                events.push(BattleEvent::TerrainEnded {
+                    terrain: terrain.kind,
+                });
+                self.field.terrain = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        field::{Terrain, Weather},
+        rng::BattleRng,
+        stats::Stats,
+        Action, Battle, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::{
+        fields::{TerrainKind, WeatherKind},
+        moves::StatusKind,
+        Dex,
+    };
+    use crate::DATA_DIRECTORY;
+
+    fn creature(species: &str, ability: &str, move_id: &str) -> BattleCreature {
+        let mut creature = BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        );
+        creature.ability = String::from(ability);
+        creature
+    }
+
+    fn play_turn(battle: &mut Battle, dex: &Dex) -> Vec<BattleEvent> {
+        battle
+            .submit_action(
+                dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_weather_wears_off() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", "torrent-heart", "rain-dance")],
+                vec![creature("tidepup", "torrent-heart", "sharpen-claws")],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::WeatherStarted {
+            weather: WeatherKind::Rain
+        }));
+        assert_eq!(
+            battle.view_for(1).field.weather,
+            Some(Weather {
+                kind: WeatherKind::Rain,
+                turns_left: 4
+            })
+        );
+        //Using the move again while it rains does nothing.
+        let events = play_turn(&mut battle, &dex);
+        assert!(events.contains(&BattleEvent::NoEffect {
+            position: Position { side: 0, index: 0 }
+        }));
+        for _ in 0..2 {
+            play_turn(&mut battle, &dex);
+        }
+        let events = play_turn(&mut battle, &dex);
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::WeatherEnded {
+                weather: WeatherKind::Rain
+            })
+        );
+        assert_eq!(battle.field.weather, None);
+    }
+
+    #[test]
+    fn test_weather_changes_the_power_of_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+            ],
+            1,
+        );
+
+        assert_eq!(battle.field_power_modifier(&dex, "fire"), 1.0);
+        battle.set_weather(&dex, WeatherKind::Sun, &mut Vec::new());
+        assert_eq!(battle.field_power_modifier(&dex, "fire"), 1.5);
+        assert_eq!(battle.field_power_modifier(&dex, "water"), 0.5);
+    }
+
+    #[test]
+    fn test_sandstorms_hurt_creatures_which_are_not_immune() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", "sturdy-shell", "sandstorm")],
+                vec![creature("tidepup", "torrent-heart", "sharpen-claws")],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::HurtByWeather {
+            position: Position { side: 1, index: 0 },
+            weather: WeatherKind::Sandstorm,
+            amount: 10,
+            remaining_hp: 150
+        }));
+        assert_eq!(battle.sides[0].creature_at(0).current_hp, 160);
+    }
+
+    #[test]
+    fn test_abilities_set_the_weather() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pyrolisk", "sun-caller", "ember")],
+                vec![creature("tidepup", "torrent-heart", "tackle")],
+            ],
+            1,
+        );
+
+        let events = battle.start(&dex);
+
+        assert_eq!(
+            events,
+            vec![
+                BattleEvent::AbilityTriggered {
+                    position: Position { side: 0, index: 0 },
+                    ability: String::from("sun-caller")
+                },
+                BattleEvent::WeatherStarted {
+                    weather: WeatherKind::Sun
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_terrain_blocks_statuses() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("gloomwisp", "hovering", "misty-terrain")],
+                vec![creature("sproutle", "overgrowth", "sleep-powder")],
+            ],
+            1,
+        );
+        battle.field.terrain = Some(Terrain {
+            kind: TerrainKind::Misty,
+            turns_left: 5,
+        });
+        let mut events = Vec::new();
+
+        let applied = battle.apply_status(
+            &dex,
+            &mut BattleRng::new(1),
+            Position { side: 0, index: 0 },
+            StatusKind::Sleep,
+            &mut events,
+        );
+
+        assert!(!applied);
+        assert_eq!(
+            events,
+            vec![BattleEvent::PreventedByTerrain {
+                position: Position { side: 0, index: 0 },
+                terrain: TerrainKind::Misty
+            }]
+        );
+    }
+
+    #[test]
+    fn test_terrain_blocks_priority_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("cindercub", "blaze-heart", "quick-jab")],
+                vec![creature("mindmoth", "hovering", "psychic-terrain")],
+            ],
+            1,
+        );
+
+        let first = play_turn(&mut battle, &dex);
+        let second = play_turn(&mut battle, &dex);
+
+        assert!(!first
+            .iter()
+            .any(|event| matches!(event, BattleEvent::PreventedByTerrain { .. })));
+        assert!(second.contains(&BattleEvent::PreventedByTerrain {
+            position: Position { side: 1, index: 0 },
+            terrain: TerrainKind::Psychic
+        }));
+        assert!(battle.sides[1].creature_at(0).current_hp < 160);
+    }
+}
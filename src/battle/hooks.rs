@@ -0,0 +1,262 @@
+use crate::battle::{
+    order::{order_actions, ActionBracket, TurnAction},
+    rng::BattleRng,
+    Battle, BattleEvent, Position,
+};
+use crate::dex::{
+    moves::{BoostableStat, Move, StatusKind},
+    Dex, DexError,
+};
+use rocket::serde::{
+    de::DeserializeOwned,
+    json::serde_json::{self, Value},
+};
+
+//Abilities and held items (and anything else reacting to what happens in a battle) implement this trait.
+//Every hook does nothing by default, so a handler only implements the events it cares about.
+//The position passed in the context is always the position of the creature the handler belongs to.
+//Adding a new behaviour means writing a small handler in abilities.rs or items.rs and naming it in its build_handler.
+//Everything else about an ability or item comes from the data files.
+pub trait EffectHandler: Send + Sync {
+    fn on_switch_in(&self, _battle: &mut Battle, _context: &mut HookContext) {}
+
+    //Called after the move in the given slot has been used, whether it hit or not.
+    fn on_move_used(&self, _battle: &mut Battle, _context: &mut HookContext, _slot: usize) {}
+
+    //Can change the effectiveness, add a modifier or raise the critical hit stage before the damage gets calculated.
+    fn before_damage(&self, _battle: &Battle, _context: &mut HookContext, _hit: &mut Hit) {}
+
+    fn after_damage(&self, _battle: &mut Battle, _context: &mut HookContext, _hit: &Hit) {}
+
+    fn on_end_of_turn(&self, _battle: &mut Battle, _context: &mut HookContext) {}
+
+    //Returning false prevents the status from being applied to the creature.
+    fn allows_status(
+        &self,
+        _battle: &Battle,
+        _context: &mut HookContext,
+        _kind: StatusKind,
+    ) -> bool {
+        true
+    }
+
+    //Passive multiplier for one of the stats of the creature.
+    fn stat_multiplier(&self, _stat: BoostableStat) -> f32 {
+        1.0
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum EffectSource {
+    Ability,
+    Item,
+}
+
+pub struct HookContext<'a> {
+    pub(crate) dex: &'a Dex,
+    pub(crate) rng: &'a mut BattleRng,
+    pub(crate) events: &'a mut Vec<BattleEvent>,
+    pub(crate) position: Position,
+    pub(crate) source: EffectSource,
+    pub(crate) source_id: &'a str,
+    triggered: bool,
+    consumed: bool,
+}
+
+//A single move hitting a single target.
+pub struct Hit<'a> {
+    pub(crate) attacker: Position,
+    pub(crate) defender: Position,
+    pub(crate) used_move: &'a Move,
+    pub(crate) effectiveness: f32,
+    pub(crate) modifier: f32,
+    pub(crate) critical_stage: u8,
+    //Only known once the damage has been dealt.
+    pub(crate) dealt: u16,
+}
+
+impl HookContext<'_> {
+    //Lets both players know the ability or item did something, which also reveals it to the opponent.
+    pub fn announce(&mut self) {
+        self.triggered = true;
+        let position = self.position;
+        let id = String::from(self.source_id);
+        self.events.push(match self.source {
+            EffectSource::Ability => BattleEvent::AbilityTriggered {
+                position,
+                ability: id,
+            },
+            EffectSource::Item => BattleEvent::ItemTriggered { position, item: id },
+        });
+    }
+
+    //Single use items are removed from the creature once the hook is done.
+    pub fn consume(&mut self) {
+        if self.source == EffectSource::Item {
+            self.consumed = true;
+            self.events.push(BattleEvent::ItemConsumed {
+                position: self.position,
+                item: String::from(self.source_id),
+            });
+        }
+    }
+}
+
+//Turns the parameters from the data files into the handler named by an ability or item.
+pub fn parse_handler<T: EffectHandler + DeserializeOwned + 'static>(
+    id: &str,
+    parameters: &Value,
+) -> Result<Box<dyn EffectHandler>, DexError> {
+    match serde_json::from_value::<T>(parameters.clone()) {
+        Ok(handler) => Ok(Box::new(handler)),
+        Err(err) => Err(DexError {
+            message: format!("{} has invalid parameters: {}", id, err),
+        }),
+    }
+}
+
+impl Battle {
+    //Runs a hook for every handler of a creature and reveals the ones which announced themselves.
+    fn run_hooks<F>(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+        mut hook: F,
+    ) where
+        F: FnMut(&dyn EffectHandler, &mut Battle, &mut HookContext),
+    {
+        let side = &self.sides[position.side];
+        let team_index = side.active[position.index];
+        for (source, source_id, handler) in side.team[team_index].handlers(dex) {
+            let mut context = HookContext {
+                dex,
+                rng,
+                events,
+                position,
+                source,
+                source_id,
+                triggered: false,
+                consumed: false,
+            };
+            hook(handler, self, &mut context);
+            let creature = &mut self.sides[position.side].team[team_index];
+            match source {
+                EffectSource::Ability if context.triggered => creature.ability_revealed = true,
+                EffectSource::Item if context.triggered => creature.item_revealed = true,
+                _ => {}
+            }
+            if context.consumed {
+                creature.item = None;
+            }
+        }
+    }
+
+    //The leads enter the field before the first turn, so their switch-in effects happen here.
+    pub fn start(&mut self, dex: &Dex) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        let mut rng = BattleRng::for_turn(self.seed, 0);
+        let leads: Vec<TurnAction> = self
+            .positions()
+            .into_iter()
+            .map(|position| TurnAction {
+                position,
+                bracket: ActionBracket::Switch,
+                speed: self
+                    .creature(position)
+                    .effective_stat(dex, BoostableStat::Speed),
+            })
+            .collect();
+        for ordered in order_actions(&leads, &mut rng) {
+            self.switch_in_hooks(dex, &mut rng, ordered.position, &mut events);
+        }
+        events
+    }
+
+    pub(super) fn switch_in_hooks(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+            handler.on_switch_in(battle, context)
+        });
+    }
+
+    pub(super) fn move_used_hooks(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        slot: usize,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+            handler.on_move_used(battle, context, slot)
+        });
+    }
+
+    pub(super) fn before_damage_hooks(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        hit: &mut Hit,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        for position in [hit.attacker, hit.defender] {
+            self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+                handler.before_damage(battle, context, hit)
+            });
+        }
+    }
+
+    pub(super) fn after_damage_hooks(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        hit: &Hit,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        for position in [hit.attacker, hit.defender] {
+            if self.is_over() {
+                return;
+            }
+            self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+                handler.after_damage(battle, context, hit)
+            });
+        }
+    }
+
+    pub(super) fn end_of_turn_hooks(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        if self.creature(position).is_fainted() {
+            return;
+        }
+        self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+            handler.on_end_of_turn(battle, context)
+        });
+    }
+
+    pub(super) fn status_allowed(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        kind: StatusKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let mut allowed = true;
+        self.run_hooks(dex, rng, position, events, |handler, battle, context| {
+            allowed &= handler.allows_status(battle, context, kind)
+        });
+        allowed
+    }
+}
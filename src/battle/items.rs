@@ -0,0 +1,410 @@
+use crate::battle::{
+    hooks::{parse_handler, EffectHandler, Hit, HookContext},
+    Battle, BattleEvent,
+};
+use crate::dex::{
+    items::Item,
+    moves::{BoostableStat, StatusKind},
+    statuses::Fraction,
+    DexError,
+};
+use rocket::serde::{de::DeserializeOwned, Deserialize};
+use std::collections::BTreeMap;
+
+pub struct LoadedItem {
+    pub(crate) data: Item,
+    handler: Box<dyn EffectHandler>,
+}
+
+pub struct ItemRegistry {
+    items: BTreeMap<String, LoadedItem>,
+}
+
+impl LoadedItem {
+    pub fn handler(&self) -> &dyn EffectHandler {
+        self.handler.as_ref()
+    }
+}
+
+impl ItemRegistry {
+    pub fn new(items: Vec<Item>) -> Result<Self, DexError> {
+        let mut registry = BTreeMap::new();
+        for entry in items {
+            if registry.contains_key(&entry.id) {
+                return Err(DexError {
+                    message: format!("Item {} is defined more than once.", entry.id),
+                });
+            }
+            let handler = build_handler(&entry)?;
+            registry.insert(
+                entry.id.clone(),
+                LoadedItem {
+                    data: entry,
+                    handler,
+                },
+            );
+        }
+        Ok(ItemRegistry { items: registry })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LoadedItem> {
+        self.items.get(id)
+    }
+}
+
+fn build_handler(item: &Item) -> Result<Box<dyn EffectHandler>, DexError> {
+    match item.handler.as_str() {
+        "DamageBoost" => parse::<DamageBoost>(item),
+        "ChoiceBoost" => parse::<ChoiceBoost>(item),
+        "CriticalBoost" => parse::<CriticalBoost>(item),
+        "HealingBerry" => parse::<HealingBerry>(item),
+        "CureBerry" => parse::<CureBerry>(item),
+        unknown => Err(DexError {
+            message: format!("Item {} uses the unknown handler {}.", item.id, unknown),
+        }),
+    }
+}
+
+fn parse<T: EffectHandler + DeserializeOwned + 'static>(
+    item: &Item,
+) -> Result<Box<dyn EffectHandler>, DexError> {
+    parse_handler::<T>(&format!("Item {}", item.id), &item.parameters)
+}
+
+//Powers up damaging moves, either all of them or only the ones of a single type.
+#[derive(Deserialize)]
+struct DamageBoost {
+    move_type: Option<String>,
+    multiplier: f32,
+}
+
+impl EffectHandler for DamageBoost {
+    fn before_damage(&self, _battle: &Battle, context: &mut HookContext, hit: &mut Hit) {
+        let matches_type = self
+            .move_type
+            .as_ref()
+            .is_none_or(|move_type| *move_type == hit.used_move.move_type);
+        if hit.attacker == context.position && matches_type {
+            hit.modifier *= self.multiplier;
+        }
+    }
+}
+
+//Raises the critical hit stage of every move of the holder.
+#[derive(Deserialize)]
+struct CriticalBoost {
+    stages: u8,
+}
+
+impl EffectHandler for CriticalBoost {
+    fn before_damage(&self, _battle: &Battle, context: &mut HookContext, hit: &mut Hit) {
+        if hit.attacker == context.position {
+            hit.critical_stage = hit.critical_stage.saturating_add(self.stages);
+        }
+    }
+}
+
+//Boosts a stat, but locks the creature into the first move it uses.
+#[derive(Deserialize)]
+struct ChoiceBoost {
+    stat: BoostableStat,
+    multiplier: f32,
+}
+
+impl EffectHandler for ChoiceBoost {
+    fn on_move_used(&self, battle: &mut Battle, context: &mut HookContext, slot: usize) {
+        let creature = battle.creature_mut(context.position);
+        if creature.locked_move.is_none() {
+            creature.locked_move = Some(slot);
+        }
+    }
+
+    fn stat_multiplier(&self, stat: BoostableStat) -> f32 {
+        if stat == self.stat {
+            self.multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+//Eaten once the creature drops to the threshold, checked after every hit and at the end of the turn.
+#[derive(Deserialize)]
+struct HealingBerry {
+    threshold: Fraction,
+    heal: Fraction,
+}
+
+impl HealingBerry {
+    fn try_eat(&self, battle: &mut Battle, context: &mut HookContext) {
+        let creature = battle.creature_mut(context.position);
+        if creature.is_fainted() || creature.current_hp > self.threshold.of(creature.stats.hp) {
+            return;
+        }
+        context.announce();
+        let healed = creature.heal(self.heal.of(creature.stats.hp));
+        context.events.push(BattleEvent::Healed {
+            position: context.position,
+            amount: healed,
+            current_hp: creature.current_hp,
+        });
+        context.consume();
+    }
+}
+
+impl EffectHandler for HealingBerry {
+    fn after_damage(&self, battle: &mut Battle, context: &mut HookContext, hit: &Hit) {
+        if hit.defender == context.position {
+            self.try_eat(battle, context);
+        }
+    }
+
+    fn on_end_of_turn(&self, battle: &mut Battle, context: &mut HookContext) {
+        self.try_eat(battle, context);
+    }
+}
+
+#[derive(Deserialize)]
+struct CureBerry {
+    statuses: Vec<StatusKind>,
+}
+
+impl EffectHandler for CureBerry {
+    fn on_end_of_turn(&self, battle: &mut Battle, context: &mut HookContext) {
+        let status = battle.creature(context.position).status;
+        if status.is_some_and(|status| self.statuses.contains(&status.kind)) {
+            context.announce();
+            battle.cure_status(context.position, context.events);
+            context.consume();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        items::ItemRegistry,
+        stats::Stats,
+        status::Status,
+        Action, Battle, BattleError, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::{
+        moves::{BoostableStat, StatusKind},
+        Dex,
+    };
+    use crate::DATA_DIRECTORY;
+
+    fn creature(item: Option<&str>, moves: &[&str]) -> BattleCreature {
+        let mut creature = BattleCreature::new(
+            "tidepup",
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            moves
+                .iter()
+                .map(|id| BattleMove {
+                    id: String::from(*id),
+                    pp: 35,
+                    revealed: false,
+                })
+                .collect(),
+        );
+        creature.item = item.map(String::from);
+        creature
+    }
+
+    fn play_turn(battle: &mut Battle, dex: &Dex, first: usize, second: usize) -> Vec<BattleEvent> {
+        battle
+            .submit_action(
+                dex,
+                0,
+                0,
+                Action::Move {
+                    slot: first,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                dex,
+                1,
+                0,
+                Action::Move {
+                    slot: second,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unknown_handlers_are_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut item = dex.items.get("oran-berry").unwrap().data.clone();
+        item.handler = String::from("Magic");
+
+        assert!(ItemRegistry::new(vec![item]).is_err());
+    }
+
+    #[test]
+    fn test_scarves_boost_speed() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+
+        let scarfed = creature(Some("swift-scarf"), &["tackle"]);
+
+        assert_eq!(scarfed.effective_stat(&dex, BoostableStat::Speed), 150);
+        assert_eq!(scarfed.effective_stat(&dex, BoostableStat::Attack), 100);
+    }
+
+    #[test]
+    fn test_damage_boosts_increase_damage() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut remaining_hp = Vec::new();
+
+        for item in [None, Some("power-charm")] {
+            let mut battle = Battle::new(
+                BattleFormat::Singles,
+                [
+                    vec![creature(item, &["tackle"])],
+                    vec![creature(None, &["sharpen-claws"])],
+                ],
+                5,
+            );
+            play_turn(&mut battle, &dex, 0, 0);
+            remaining_hp.push(battle.sides[1].creature_at(0).current_hp);
+        }
+
+        assert!(remaining_hp[1] < remaining_hp[0]);
+    }
+
+    #[test]
+    fn test_critical_boosts_land_more_critical_hits() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut critical_hits = Vec::new();
+
+        for item in [None, Some("scope-lens")] {
+            let mut count = 0;
+            for seed in 0..20 {
+                let mut battle = Battle::new(
+                    BattleFormat::Singles,
+                    [
+                        vec![creature(item, &["karate-chop"])],
+                        vec![creature(None, &["sharpen-claws"])],
+                    ],
+                    seed,
+                );
+                let events = play_turn(&mut battle, &dex, 0, 0);
+                count += events
+                    .iter()
+                    .filter(|event| matches!(event, BattleEvent::CriticalHit { .. }))
+                    .count();
+            }
+            critical_hits.push(count);
+        }
+
+        assert!(critical_hits[1] > critical_hits[0]);
+    }
+
+    #[test]
+    fn test_choice_items_lock_the_first_move() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![
+                    creature(Some("choice-band"), &["tackle", "water-gun"]),
+                    creature(None, &["tackle"]),
+                ],
+                vec![creature(None, &["sharpen-claws"])],
+            ],
+            1,
+        );
+
+        play_turn(&mut battle, &dex, 0, 0);
+
+        assert_eq!(battle.sides[0].creature_at(0).locked_move, Some(0));
+        assert!(matches!(
+            battle.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 1,
+                    target: None,
+                }
+            ),
+            Err(BattleError::InvalidAction(_))
+        ));
+        battle
+            .submit_action(&dex, 0, 0, Action::Switch { to: 1 })
+            .unwrap();
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(battle.sides[0].team[0].locked_move, None);
+    }
+
+    #[test]
+    fn test_berries_are_eaten_at_the_threshold() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut hurt = creature(Some("oran-berry"), &["sharpen-claws"]);
+        hurt.current_hp = 70;
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [vec![hurt], vec![creature(None, &["sharpen-claws"])]],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex, 0, 0);
+
+        assert!(events.contains(&BattleEvent::Healed {
+            position: Position { side: 0, index: 0 },
+            amount: 40,
+            current_hp: 110
+        }));
+        assert!(events.contains(&BattleEvent::ItemConsumed {
+            position: Position { side: 0, index: 0 },
+            item: String::from("oran-berry")
+        }));
+        assert_eq!(battle.sides[0].creature_at(0).item, None);
+        assert!(battle.sides[0].creature_at(0).item_revealed);
+    }
+
+    #[test]
+    fn test_cure_berries_remove_the_status() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut poisoned = creature(Some("lum-berry"), &["sharpen-claws"]);
+        poisoned.status = Some(Status {
+            kind: StatusKind::Poison,
+            turns_left: None,
+        });
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [vec![poisoned], vec![creature(None, &["sharpen-claws"])]],
+            1,
+        );
+
+        play_turn(&mut battle, &dex, 0, 0);
+
+        assert_eq!(battle.sides[0].creature_at(0).status, None);
+        assert_eq!(battle.sides[0].creature_at(0).item, None);
+    }
+}
@@ -0,0 +1,1177 @@
+pub mod abilities;
+pub mod accuracy;
+pub mod charge;
+pub mod creature;
+pub mod damage;
+pub mod field;
+pub mod hooks;
+pub mod items;
+pub mod move_effects;
+pub mod order;
+pub mod paste;
+pub mod rng;
+pub mod side_conditions;
+pub mod stats;
+pub mod status;
+pub mod targeting;
+pub mod team;
+mod turn;
+pub mod validation;
+pub mod view;
+pub mod volatile;
+
+use crate::dex::{
+    fields::{TerrainKind, WeatherKind},
+    moves::{BoostableStat, MoveCategory, StatusKind},
+    side_conditions::SideConditionKind,
+    Dex,
+};
+use creature::BattleCreature;
+use field::Field;
+use order::OrderedAction;
+use rocket::serde::{Deserialize, Serialize};
+use side_conditions::ActiveSideCondition;
+use validation::TeamError;
+use volatile::VolatileKind;
+
+//The battle engine is deliberately independent of the database and the API.
+//A Game owns a Battle and persists it, everything in here just mutates plain data.
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Battle {
+    pub(crate) turn: u32,
+    pub(crate) phase: BattlePhase,
+    pub(crate) format: BattleFormat,
+    pub(crate) sides: [Side; 2],
+    pub(crate) field: Field,
+    pub(crate) winner: Option<usize>,
+    pub(crate) seed: u32,
+    //Every accepted submission in order. Together with the seed this is enough to replay the battle.
+    pub(crate) history: Vec<LoggedAction>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub enum BattleFormat {
+    #[default]
+    Singles,
+    Doubles,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum BattlePhase {
+    WaitingForActions,
+    //At least one active creature fainted and its side has to pick a replacement before the next turn.
+    WaitingForReplacement,
+    Finished,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Side {
+    pub(crate) team: Vec<BattleCreature>,
+    //The team index of the creature in each position, one position per creature on the field.
+    pub(crate) active: Vec<usize>,
+    pub(crate) pending_actions: Vec<Option<Action>>,
+    //The positions whose creature fainted and which can be refilled from the bench.
+    pub(crate) needs_replacement: Vec<usize>,
+    pub(crate) conditions: Vec<ActiveSideCondition>,
+    //Team slots in the order they were first seen on the field. The opponent gets to know the
+    //creatures in this order, so their index in the view never changes.
+    pub(crate) reveal_order: Vec<usize>,
+}
+
+//One of the spots on the field, the index points into the active creatures of the side.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Position {
+    pub(crate) side: usize,
+    pub(crate) index: usize,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Action {
+    //The target only matters for moves hitting a single creature in doubles, it is picked
+    //automatically when left out.
+    Move {
+        slot: usize,
+        #[serde(default)]
+        target: Option<Position>,
+    },
+    Switch {
+        to: usize,
+    },
+    Forfeit,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct LoggedAction {
+    pub(crate) side: usize,
+    #[serde(default)]
+    pub(crate) position: usize,
+    pub(crate) action: Action,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub enum BattleEvent {
+    TurnOrder {
+        order: Vec<OrderedAction>,
+    },
+    UsedMove {
+        #[serde(flatten)]
+        position: Position,
+        move_id: String,
+    },
+    ChargingMove {
+        #[serde(flatten)]
+        position: Position,
+        move_id: String,
+    },
+    Damaged {
+        #[serde(flatten)]
+        position: Position,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    Missed {
+        #[serde(flatten)]
+        position: Position,
+    },
+    CriticalHit {
+        #[serde(flatten)]
+        position: Position,
+    },
+    NoEffect {
+        #[serde(flatten)]
+        position: Position,
+    },
+    HitTimes {
+        #[serde(flatten)]
+        position: Position,
+        hits: u8,
+    },
+    OneHitKnockOut {
+        #[serde(flatten)]
+        position: Position,
+    },
+    HurtByRecoil {
+        #[serde(flatten)]
+        position: Position,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    StatusApplied {
+        #[serde(flatten)]
+        position: Position,
+        status: StatusKind,
+    },
+    StatusCured {
+        #[serde(flatten)]
+        position: Position,
+        status: StatusKind,
+    },
+    PreventedByStatus {
+        #[serde(flatten)]
+        position: Position,
+        status: StatusKind,
+    },
+    VolatileStarted {
+        #[serde(flatten)]
+        position: Position,
+        volatile: VolatileKind,
+    },
+    VolatileEnded {
+        #[serde(flatten)]
+        position: Position,
+        volatile: VolatileKind,
+    },
+    PreventedByVolatile {
+        #[serde(flatten)]
+        position: Position,
+        volatile: VolatileKind,
+    },
+    ProtectedFrom {
+        #[serde(flatten)]
+        position: Position,
+    },
+    WeatherStarted {
+        weather: WeatherKind,
+    },
+    WeatherEnded {
+        weather: WeatherKind,
+    },
+    HurtByWeather {
+        #[serde(flatten)]
+        position: Position,
+        weather: WeatherKind,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    TerrainStarted {
+        terrain: TerrainKind,
+    },
+    TerrainEnded {
+        terrain: TerrainKind,
+    },
+    PreventedByTerrain {
+        #[serde(flatten)]
+        position: Position,
+        terrain: TerrainKind,
+    },
+    SideConditionStarted {
+        side: usize,
+        condition: SideConditionKind,
+    },
+    SideConditionEnded {
+        side: usize,
+        condition: SideConditionKind,
+    },
+    HurtByHazard {
+        #[serde(flatten)]
+        position: Position,
+        condition: SideConditionKind,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    StatStageChanged {
+        #[serde(flatten)]
+        position: Position,
+        stat: BoostableStat,
+        change: i8,
+        stage: i8,
+    },
+    Drained {
+        #[serde(flatten)]
+        position: Position,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    Healed {
+        #[serde(flatten)]
+        position: Position,
+        amount: u16,
+        current_hp: u16,
+    },
+    HurtByStatus {
+        #[serde(flatten)]
+        position: Position,
+        status: StatusKind,
+        amount: u16,
+        remaining_hp: u16,
+    },
+    Fainted {
+        #[serde(flatten)]
+        position: Position,
+        creature: usize,
+    },
+    SwitchedOut {
+        #[serde(flatten)]
+        position: Position,
+        creature: usize,
+    },
+    SwitchedIn {
+        #[serde(flatten)]
+        position: Position,
+        creature: usize,
+    },
+    WaitingForReplacement {
+        #[serde(flatten)]
+        position: Position,
+    },
+    AbilityTriggered {
+        #[serde(flatten)]
+        position: Position,
+        ability: String,
+    },
+    ItemTriggered {
+        #[serde(flatten)]
+        position: Position,
+        item: String,
+    },
+    ItemConsumed {
+        #[serde(flatten)]
+        position: Position,
+        item: String,
+    },
+    Forfeited {
+        side: usize,
+    },
+    Won {
+        side: usize,
+    },
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum BattleError {
+    NotStarted,
+    BattleOver,
+    AlreadySubmitted,
+    //Lead orders can only be picked while both teams are shown in the team preview.
+    NotInPreview,
+    //The creature is charging or recharging, so its action gets submitted automatically.
+    LockedIn,
+    InvalidAction(String),
+    InvalidTeam(String),
+    //The team breaks the rules of the format, every broken rule is listed.
+    RuleViolations(Vec<TeamError>),
+}
+
+impl BattleError {
+    pub fn message(&self) -> String {
+        match self {
+            BattleError::NotStarted => String::from("The battle has not started yet."),
+            BattleError::BattleOver => String::from("The battle is already over."),
+            BattleError::AlreadySubmitted => {
+                String::from("An action was already submitted for this turn.")
+            }
+            BattleError::NotInPreview => String::from("The game is not in the team preview."),
+            BattleError::LockedIn => String::from(
+                "The creature is locked into its move, its action gets submitted automatically.",
+            ),
+            BattleError::InvalidAction(reason) | BattleError::InvalidTeam(reason) => reason.clone(),
+            BattleError::RuleViolations(errors) => {
+                format!("The team breaks {} rules of the format.", errors.len())
+            }
+        }
+    }
+}
+
+impl BattleFormat {
+    pub const ALL: [BattleFormat; 2] = [BattleFormat::Singles, BattleFormat::Doubles];
+
+    pub fn active_per_side(&self) -> usize {
+        match self {
+            BattleFormat::Singles => 1,
+            BattleFormat::Doubles => 2,
+        }
+    }
+}
+
+impl Side {
+    pub fn new(team: Vec<BattleCreature>, active_per_side: usize) -> Self {
+        let active: Vec<usize> = (0..active_per_side.min(team.len())).collect();
+        let mut side = Side {
+            team,
+            pending_actions: vec![None; active.len()],
+            active: active.clone(),
+            needs_replacement: Vec::new(),
+            conditions: Vec::new(),
+            reveal_order: Vec::new(),
+        };
+        for lead in active {
+            side.reveal(lead);
+        }
+        side
+    }
+
+    pub fn reveal(&mut self, creature: usize) {
+        if !self.team[creature].revealed {
+            self.team[creature].revealed = true;
+            self.reveal_order.push(creature);
+        }
+    }
+
+    pub fn creature_at(&self, index: usize) -> &BattleCreature {
+        &self.team[self.active[index]]
+    }
+
+    pub fn has_usable_creatures(&self) -> bool {
+        self.team.iter().any(|creature| !creature.is_fainted())
+    }
+
+    //The creatures which could be switched in.
+    pub fn bench(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.team.len())
+            .filter(|index| !self.active.contains(index) && !self.team[*index].is_fainted())
+    }
+
+    //Only the positions which are asked for something have to submit an action.
+    fn positions_to_act(&self, phase: BattlePhase) -> Vec<usize> {
+        match phase {
+            BattlePhase::WaitingForActions => (0..self.active.len())
+                .filter(|index| !self.creature_at(*index).is_fainted())
+                .collect(),
+            BattlePhase::WaitingForReplacement => self.needs_replacement.clone(),
+            BattlePhase::Finished => Vec::new(),
+        }
+    }
+
+    //A forfeit counts for the whole side.
+    fn has_submitted(&self, phase: BattlePhase) -> bool {
+        self.pending_actions.contains(&Some(Action::Forfeit))
+            || self
+                .positions_to_act(phase)
+                .iter()
+                .all(|index| self.pending_actions[*index].is_some())
+    }
+}
+
+impl Battle {
+    pub fn new(format: BattleFormat, teams: [Vec<BattleCreature>; 2], seed: u32) -> Self {
+        let [first, second] = teams;
+        let active_per_side = format.active_per_side();
+        Battle {
+            turn: 1,
+            phase: BattlePhase::WaitingForActions,
+            format,
+            sides: [
+                Side::new(first, active_per_side),
+                Side::new(second, active_per_side),
+            ],
+            field: Field::default(),
+            winner: None,
+            seed,
+            history: Vec::new(),
+        }
+    }
+
+    //Starts from a fresh battle and feeds it the same submissions again.
+    pub fn replay(
+        start: &Battle,
+        history: &[LoggedAction],
+        dex: &Dex,
+    ) -> Result<Battle, BattleError> {
+        let mut battle = start.clone();
+        for logged in history {
+            battle.submit_action(dex, logged.side, logged.position, logged.action)?;
+        }
+        Ok(battle)
+    }
+
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.phase == BattlePhase::Finished
+    }
+
+    pub fn creature(&self, position: Position) -> &BattleCreature {
+        self.sides[position.side].creature_at(position.index)
+    }
+
+    pub fn creature_mut(&mut self, position: Position) -> &mut BattleCreature {
+        let side = &mut self.sides[position.side];
+        &mut side.team[side.active[position.index]]
+    }
+
+    //Every position on the field, in the order the sides are stored.
+    pub fn positions(&self) -> Vec<Position> {
+        (0..self.sides.len())
+            .flat_map(|side| {
+                (0..self.sides[side].active.len()).map(move |index| Position { side, index })
+            })
+            .collect()
+    }
+
+    pub fn foes(&self, position: Position) -> Vec<Position> {
+        self.positions()
+            .into_iter()
+            .filter(|other| other.side != position.side)
+            .collect()
+    }
+
+    pub fn allies(&self, position: Position) -> Vec<Position> {
+        self.positions()
+            .into_iter()
+            .filter(|other| other.side == position.side && *other != position)
+            .collect()
+    }
+
+    //Stores the action for one position of a side. Once every position which has to act has an
+    //action, the turn (or the replacement of fainted creatures) gets resolved and its events are returned.
+    pub fn submit_action(
+        &mut self,
+        dex: &Dex,
+        side: usize,
+        position: usize,
+        action: Action,
+    ) -> Result<Option<Vec<BattleEvent>>, BattleError> {
+        if self.is_over() {
+            return Err(BattleError::BattleOver);
+        }
+        let Some(own_side) = self.sides.get(side) else {
+            return Err(BattleError::InvalidAction(format!(
+                "Side {} does not exist.",
+                side
+            )));
+        };
+        let positions = own_side.positions_to_act(self.phase);
+        if positions.is_empty() {
+            return Err(BattleError::InvalidAction(String::from(
+                "You have to wait for your opponent to pick a replacement.",
+            )));
+        }
+        if !positions.contains(&position) {
+            return Err(BattleError::InvalidAction(format!(
+                "Position {} doesn't have to act right now.",
+                position
+            )));
+        }
+        let at = Position {
+            side,
+            index: position,
+        };
+        if self.phase == BattlePhase::WaitingForActions && self.creature(at).forced_action.is_some()
+        {
+            return Err(BattleError::LockedIn);
+        }
+        if own_side.pending_actions[position].is_some() {
+            return Err(BattleError::AlreadySubmitted);
+        }
+        self.validate_action(dex, at, &action)?;
+        self.sides[side].pending_actions[position] = Some(action);
+        self.history.push(LoggedAction {
+            side,
+            position,
+            action,
+        });
+
+        let phase = self.phase;
+        let everyone_submitted = self.sides.iter().all(|side| side.has_submitted(phase));
+        if !everyone_submitted {
+            return Ok(None);
+        }
+        let mut events = match phase {
+            BattlePhase::WaitingForReplacement => self.resolve_replacements(dex),
+            _ => self.resolve_turn(dex),
+        };
+        //When every creature on the field is locked in, nobody has to submit anything for the next turn.
+        while self.phase == BattlePhase::WaitingForActions
+            && self
+                .sides
+                .iter()
+                .all(|side| side.has_submitted(BattlePhase::WaitingForActions))
+        {
+            events.extend(self.resolve_turn(dex));
+        }
+        Ok(Some(events))
+    }
+
+    fn validate_action(
+        &self,
+        dex: &Dex,
+        position: Position,
+        action: &Action,
+    ) -> Result<(), BattleError> {
+        let own_side = &self.sides[position.side];
+        let creature = self.creature(position);
+        match action {
+            Action::Move { .. } if self.phase == BattlePhase::WaitingForReplacement => {
+                Err(BattleError::InvalidAction(String::from(
+                    "Your active creature fainted, you have to switch.",
+                )))
+            }
+            Action::Move { slot, target } => match creature.moves.get(*slot) {
+                None => Err(BattleError::InvalidAction(format!(
+                    "The active creature has no move in slot {}.",
+                    slot
+                ))),
+                Some(chosen) => match dex.moves.get(&chosen.id) {
+                    None => Err(BattleError::InvalidAction(format!(
+                        "{} is not a known move.",
+                        chosen.id
+                    ))),
+                    Some(_) if chosen.pp == 0 => Err(BattleError::InvalidAction(format!(
+                        "{} has no PP left.",
+                        chosen.id
+                    ))),
+                    Some(_) if creature.locked_move.is_some_and(|locked| locked != *slot) => {
+                        Err(BattleError::InvalidAction(format!(
+                            "The active creature is locked into its move and can't use {}.",
+                            chosen.id
+                        )))
+                    }
+                    Some(known)
+                        if self.has_volatile(position, VolatileKind::Taunt)
+                            && known.category == MoveCategory::Status =>
+                    {
+                        Err(BattleError::InvalidAction(format!(
+                            "The active creature is taunted and can't use {}.",
+                            chosen.id
+                        )))
+                    }
+                    Some(known) => self.validate_target(position, known.target, *target),
+                },
+            },
+            Action::Switch { to } => match own_side.team.get(*to) {
+                None => Err(BattleError::InvalidAction(format!(
+                    "There is no creature in slot {}.",
+                    to
+                ))),
+                Some(_) if own_side.active.contains(to) => Err(BattleError::InvalidAction(
+                    String::from("That creature is already on the field."),
+                )),
+                Some(creature) if creature.is_fainted() => Err(BattleError::InvalidAction(
+                    String::from("That creature has fainted."),
+                )),
+                Some(_) if own_side.pending_actions.contains(&Some(*action)) => {
+                    Err(BattleError::InvalidAction(String::from(
+                        "That creature is already switching in.",
+                    )))
+                }
+                Some(_) => Ok(()),
+            },
+            Action::Forfeit => Ok(()),
+        }
+    }
+
+    fn declare_winner(&mut self, side: usize, events: &mut Vec<BattleEvent>) {
+        self.winner = Some(side);
+        self.phase = BattlePhase::Finished;
+        events.push(BattleEvent::Won { side });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::battle::{creature::BattleMove, order::OrderReason, stats::Stats};
+    use crate::DATA_DIRECTORY;
+
+    fn creature(hp: u16, speed: u16, move_id: &str) -> BattleCreature {
+        creature_of("tidepup", hp, speed, move_id)
+    }
+
+    fn creature_of(species: &str, hp: u16, speed: u16, move_id: &str) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_turn_resolves_after_both_sides_submitted() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        let first = battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        assert!(first.is_none());
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .expect("Turn should have been resolved.");
+
+        assert_eq!(
+            events[1],
+            BattleEvent::UsedMove {
+                position: Position { side: 0, index: 0 },
+                move_id: String::from("tackle")
+            }
+        );
+        assert_eq!(battle.turn, 2);
+        assert!(battle.sides[0].creature_at(0).current_hp < 200);
+        assert!(battle.sides[1].creature_at(0).current_hp < 200);
+        assert_eq!(battle.sides[0].creature_at(0).moves[0].pp, 34);
+    }
+
+    #[test]
+    fn test_submitting_twice_is_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            battle.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                }
+            ),
+            Err(BattleError::AlreadySubmitted)
+        );
+    }
+
+    #[test]
+    fn test_invalid_move_slot_is_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        let result = battle.submit_action(
+            &dex,
+            0,
+            0,
+            Action::Move {
+                slot: 3,
+                target: None,
+            },
+        );
+
+        assert!(matches!(result, Err(BattleError::InvalidAction(_))));
+    }
+
+    #[test]
+    fn test_fainted_creature_has_to_be_replaced() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(1, 50, "tackle"), creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        assert!(events.contains(&BattleEvent::Fainted {
+            position: Position { side: 1, index: 0 },
+            creature: 0
+        }));
+        assert_eq!(
+            events.last(),
+            Some(&BattleEvent::WaitingForReplacement {
+                position: Position { side: 1, index: 0 }
+            })
+        );
+        assert_eq!(battle.phase, BattlePhase::WaitingForReplacement);
+        assert!(matches!(
+            battle.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                }
+            ),
+            Err(BattleError::InvalidAction(_))
+        ));
+        assert!(matches!(
+            battle.submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                }
+            ),
+            Err(BattleError::InvalidAction(_))
+        ));
+
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 1 })
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            events,
+            vec![BattleEvent::SwitchedIn {
+                position: Position { side: 1, index: 0 },
+                creature: 1
+            }]
+        );
+        assert_eq!(battle.sides[1].active, vec![1]);
+        assert_eq!(battle.phase, BattlePhase::WaitingForActions);
+        assert_eq!(battle.turn, 2);
+    }
+
+    #[test]
+    fn test_switching_happens_before_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "tackle"), creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 1 })
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            events[1..3],
+            [
+                BattleEvent::SwitchedOut {
+                    position: Position { side: 1, index: 0 },
+                    creature: 0
+                },
+                BattleEvent::SwitchedIn {
+                    position: Position { side: 1, index: 0 },
+                    creature: 1
+                }
+            ]
+        );
+        assert_eq!(battle.sides[1].team[0].current_hp, 200);
+        assert!(battle.sides[1].team[1].current_hp < 200);
+        assert!(battle.sides[1].team[1].revealed);
+    }
+
+    #[test]
+    fn test_turn_order_is_logged() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "quick-jab")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            events[0],
+            BattleEvent::TurnOrder {
+                order: vec![
+                    OrderedAction {
+                        position: Position { side: 1, index: 0 },
+                        reason: OrderReason::Priority
+                    },
+                    OrderedAction {
+                        position: Position { side: 0, index: 0 },
+                        reason: OrderReason::Last
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_switches_are_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut fainted = creature(200, 50, "tackle");
+        fainted.current_hp = 0;
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle"), fainted],
+                vec![creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        for to in [0, 1, 2] {
+            assert!(matches!(
+                battle.submit_action(&dex, 0, 0, Action::Switch { to }),
+                Err(BattleError::InvalidAction(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_battle_is_won_when_no_usable_creatures_are_left() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(1, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(battle.winner(), Some(0));
+        assert_eq!(events.last(), Some(&BattleEvent::Won { side: 0 }));
+        assert_eq!(
+            battle.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                }
+            ),
+            Err(BattleError::BattleOver)
+        );
+    }
+
+    #[test]
+    fn test_forfeit_ends_the_battle() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature(200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle.submit_action(&dex, 0, 0, Action::Forfeit).unwrap();
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(battle.winner(), Some(1));
+        assert_eq!(battle.sides[1].creature_at(0).current_hp, 200);
+    }
+
+    #[test]
+    fn test_immune_creatures_take_no_damage() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(200, 100, "tackle")],
+                vec![creature_of("gloomwisp", 200, 50, "tackle")],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(battle.sides[1].creature_at(0).current_hp, 200);
+        assert!(battle.sides[0].creature_at(0).current_hp < 200);
+    }
+
+    #[test]
+    fn test_same_seed_produces_same_battle() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let teams = [
+            vec![creature(300, 100, "rock-throw")],
+            vec![creature(300, 100, "rock-throw")],
+        ];
+        let mut first = Battle::new(BattleFormat::Singles, teams.clone(), 99);
+        let mut second = Battle::new(BattleFormat::Singles, teams, 99);
+
+        for _ in 0..5 {
+            for battle in [&mut first, &mut second] {
+                battle
+                    .submit_action(
+                        &dex,
+                        0,
+                        0,
+                        Action::Move {
+                            slot: 0,
+                            target: None,
+                        },
+                    )
+                    .unwrap();
+                battle
+                    .submit_action(
+                        &dex,
+                        1,
+                        0,
+                        Action::Move {
+                            slot: 0,
+                            target: None,
+                        },
+                    )
+                    .unwrap();
+            }
+        }
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_replaying_history_reproduces_the_battle() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let start = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(150, 100, "rock-throw")],
+                vec![creature(150, 100, "tackle")],
+            ],
+            12345,
+        );
+        let mut battle = start.clone();
+        while !battle.is_over() {
+            battle
+                .submit_action(
+                    &dex,
+                    0,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap();
+            battle
+                .submit_action(
+                    &dex,
+                    1,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap();
+        }
+
+        let replayed = Battle::replay(&start, &battle.history, &dex).unwrap();
+
+        assert_eq!(replayed, battle);
+    }
+}
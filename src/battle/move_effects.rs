@@ -0,0 +1,327 @@
+use crate::battle::{
+    accuracy::{roll_critical_hit, AccuracyCheck},
+    creature::BattleCreature,
+    damage::{calculate_damage, Damage, DamageRequest},
+    hooks::Hit,
+    rng::BattleRng,
+    Battle, BattleEvent, Position,
+};
+use crate::dex::{
+    moves::{BoostableStat, FixedDamage, HitCount, Move, MoveCategory},
+    Dex,
+};
+
+//Interprets the damage related parts of a move description, so new moves only need new data.
+//Moves hitting once don't advance the generator.
+pub fn roll_hit_count(hits: Option<HitCount>, rng: &mut BattleRng) -> u8 {
+    match hits {
+        None => 1,
+        Some(HitCount::Fixed(hits)) => hits,
+        Some(HitCount::Random { min, max }) => rng.range(min as u32, max as u32) as u8,
+    }
+}
+
+impl Battle {
+    //One hit knock out moves ignore the stages, but get more accurate the higher the level of the
+    //user is compared to the target.
+    pub(super) fn accuracy_check(
+        &self,
+        position: Position,
+        target: Position,
+        used_move: &Move,
+    ) -> AccuracyCheck {
+        let (attacker, defender) = (self.creature(position), self.creature(target));
+        if used_move.fixed_damage == Some(FixedDamage::OneHitKnockOut) {
+            let level_difference = attacker.level.saturating_sub(defender.level);
+            return AccuracyCheck {
+                accuracy: used_move
+                    .accuracy
+                    .map(|accuracy| accuracy.saturating_add(level_difference).min(100)),
+                accuracy_stage: 0,
+                evasion_stage: 0,
+            };
+        }
+        AccuracyCheck {
+            accuracy: used_move.accuracy,
+            accuracy_stage: attacker.stages.accuracy,
+            evasion_stage: defender.stages.evasion,
+        }
+    }
+
+    //Fixed damage can't land critical hits and ignores every modifier, but targets immune to the
+    //type of the move still take nothing.
+    pub(super) fn hit_damage(&self, dex: &Dex, rng: &mut BattleRng, hit: &Hit) -> Damage {
+        let (attacker, defender) = (self.creature(hit.attacker), self.creature(hit.defender));
+        let Some(fixed_damage) = hit.used_move.fixed_damage else {
+            let request = damage_request(dex, attacker, defender, hit);
+            let critical = roll_critical_hit(&dex.mechanics.critical_hit, hit.critical_stage, rng);
+            return calculate_damage(&request, critical, rng);
+        };
+        let amount = match fixed_damage {
+            _ if hit.effectiveness == 0.0 => 0,
+            FixedDamage::Amount(amount) => amount,
+            FixedDamage::Level => attacker.level as u16,
+            FixedDamage::OneHitKnockOut => defender.current_hp,
+        };
+        Damage {
+            amount,
+            critical: false,
+        }
+    }
+
+    //Both are based on the damage dealt by every hit of the move combined.
+    pub(super) fn recoil_and_drain(
+        &mut self,
+        position: Position,
+        used_move: &Move,
+        dealt: u16,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let user = self.creature_mut(position);
+        if dealt == 0 || user.is_fainted() {
+            return;
+        }
+        if let Some(drain) = used_move.drain {
+            let healed = user.heal(drain.of(dealt));
+            if healed > 0 {
+                events.push(BattleEvent::Healed {
+                    position,
+                    amount: healed,
+                    current_hp: user.current_hp,
+                });
+            }
+        }
+        if let Some(recoil) = used_move.recoil {
+            let amount = user.take_damage(recoil.of(dealt));
+            events.push(BattleEvent::HurtByRecoil {
+                position,
+                amount,
+                remaining_hp: user.current_hp,
+            });
+        }
+    }
+}
+
+fn damage_request(
+    dex: &Dex,
+    attacker: &BattleCreature,
+    defender: &BattleCreature,
+    hit: &Hit,
+) -> DamageRequest {
+    let used_move = hit.used_move;
+    let (attack, defense) = match used_move.category {
+        MoveCategory::Special => (BoostableStat::SpecialAttack, BoostableStat::SpecialDefense),
+        _ => (BoostableStat::Attack, BoostableStat::Defense),
+    };
+    let stab = dex
+        .species
+        .get(&attacker.species)
+        .is_some_and(|species| species.types.contains(&used_move.move_type));
+    DamageRequest {
+        level: attacker.level,
+        power: used_move.power.unwrap_or(0),
+        attack: attacker.effective_stat(dex, attack),
+        defense: defender.effective_stat(dex, defense),
+        stab,
+        effectiveness: hit.effectiveness,
+        modifier: hit.modifier,
+        critical_multiplier: dex.mechanics.critical_hit.multiplier,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        stats::Stats,
+        Action, Battle, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::Dex;
+    use crate::DATA_DIRECTORY;
+
+    const USER: Position = Position { side: 0, index: 0 };
+    const TARGET: Position = Position { side: 1, index: 0 };
+
+    fn creature(species: &str, level: u8, move_id: &str) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            level,
+            Stats {
+                hp: 200,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        )
+    }
+
+    //The user always moves first, the target only sharpens its claws.
+    fn battle(user: BattleCreature, mut target: BattleCreature, seed: u32) -> Battle {
+        target.stats.speed = 50;
+        Battle::new(BattleFormat::Singles, [vec![user], vec![target]], seed)
+    }
+
+    fn play_turn(battle: &mut Battle, dex: &Dex) -> Vec<BattleEvent> {
+        let attack = Action::Move {
+            slot: 0,
+            target: None,
+        };
+        battle.submit_action(dex, 0, 0, attack).unwrap();
+        battle.submit_action(dex, 1, 0, attack).unwrap().unwrap()
+    }
+
+    fn damage_taken(events: &[BattleEvent], position: Position) -> Vec<u16> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                BattleEvent::Damaged {
+                    position: damaged,
+                    amount,
+                    ..
+                } if *damaged == position => Some(*amount),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_hit_counts() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(
+            creature("brawlbun", 50, "double-kick"),
+            creature("tidepup", 50, "sharpen-claws"),
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert_eq!(damage_taken(&events, TARGET).len(), 2);
+        assert!(events.contains(&BattleEvent::HitTimes {
+            position: TARGET,
+            hits: 2
+        }));
+    }
+
+    #[test]
+    fn test_random_hit_counts_stay_in_range() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut counts = Vec::new();
+
+        for seed in 0..30 {
+            let mut battle = battle(
+                creature("sproutle", 50, "bullet-seed"),
+                creature("cindercub", 50, "sharpen-claws"),
+                seed,
+            );
+            let hits = damage_taken(&play_turn(&mut battle, &dex), TARGET).len();
+            assert!((2..=5).contains(&hits));
+            counts.push(hits);
+        }
+
+        assert!(counts.contains(&2));
+        assert!(counts.contains(&5));
+    }
+
+    #[test]
+    fn test_multi_hit_moves_stop_once_the_target_faints() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut target = creature("tidepup", 50, "sharpen-claws");
+        target.current_hp = 1;
+        let mut battle = battle(creature("brawlbun", 50, "double-kick"), target, 1);
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert_eq!(damage_taken(&events, TARGET), vec![1]);
+        assert!(events.contains(&BattleEvent::HitTimes {
+            position: TARGET,
+            hits: 1
+        }));
+    }
+
+    #[test]
+    fn test_recoil_is_a_fraction_of_the_damage_dealt() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(
+            creature("plumbird", 50, "double-edge"),
+            creature("tidepup", 50, "sharpen-claws"),
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex);
+
+        let dealt = damage_taken(&events, TARGET)[0];
+        assert_eq!(battle.creature(USER).current_hp, 200 - dealt / 3);
+        assert!(events.contains(&BattleEvent::HurtByRecoil {
+            position: USER,
+            amount: dealt / 3,
+            remaining_hp: 200 - dealt / 3,
+        }));
+    }
+
+    #[test]
+    fn test_drain_heals_the_user() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut user = creature("sproutle", 50, "giga-drain");
+        user.current_hp = 100;
+        let mut battle = battle(user, creature("tidepup", 50, "sharpen-claws"), 1);
+
+        let events = play_turn(&mut battle, &dex);
+
+        let dealt = damage_taken(&events, TARGET)[0];
+        assert_eq!(battle.creature(USER).current_hp, 100 + dealt / 2);
+    }
+
+    #[test]
+    fn test_level_damage_ignores_stats_but_not_immunities() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(
+            creature("brawlbun", 37, "seismic-toss"),
+            creature("tidepup", 50, "sharpen-claws"),
+            1,
+        );
+        let mut immune = battle.clone();
+        immune.sides[1].team[0] = creature("gloomwisp", 50, "sharpen-claws");
+
+        assert_eq!(
+            damage_taken(&play_turn(&mut battle, &dex), TARGET),
+            vec![37]
+        );
+        assert_eq!(damage_taken(&play_turn(&mut immune, &dex), TARGET), vec![0]);
+    }
+
+    #[test]
+    fn test_one_hit_knock_outs() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let hit_seed = (0..)
+            .find(|seed| {
+                let mut battle = battle(
+                    creature("frostfang", 50, "sheer-cold"),
+                    creature("tidepup", 50, "sharpen-claws"),
+                    *seed,
+                );
+                !play_turn(&mut battle, &dex).contains(&BattleEvent::Missed { position: TARGET })
+            })
+            .unwrap();
+        let mut battle = battle(
+            creature("frostfang", 50, "sheer-cold"),
+            creature("tidepup", 50, "sharpen-claws"),
+            hit_seed,
+        );
+        let mut higher_level = battle.clone();
+        higher_level.sides[1].team[0].level = 51;
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::OneHitKnockOut { position: TARGET }));
+        assert!(battle.creature(TARGET).is_fainted());
+        assert!(play_turn(&mut higher_level, &dex)
+            .contains(&BattleEvent::NoEffect { position: TARGET }));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::battle::{rng::BattleRng, Position};
+use rocket::serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+//Decides in which order the actions of a turn are carried out:
+//1. switches before anything else
+//2. moves by their priority bracket, higher brackets first
+//3. faster creatures first, using their effective speed
+//4. if all of that is equal the seeded rng breaks the tie
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum ActionBracket {
+    Switch,
+    Move { priority: i8 },
+}
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct TurnAction {
+    pub(crate) position: Position,
+    pub(crate) bracket: ActionBracket,
+    pub(crate) speed: u16,
+}
+
+//Why an action happens before the one after it.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum OrderReason {
+    Switch,
+    Priority,
+    Speed,
+    SpeedTie,
+    //Nothing comes after this action.
+    Last,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct OrderedAction {
+    #[serde(flatten)]
+    pub(crate) position: Position,
+    pub(crate) reason: OrderReason,
+}
+
+impl ActionBracket {
+    fn rank(&self) -> i16 {
+        match self {
+            ActionBracket::Switch => i16::MAX,
+            ActionBracket::Move { priority } => *priority as i16,
+        }
+    }
+}
+
+impl TurnAction {
+    //Ordering::Greater means self acts first.
+    fn compare(&self, other: &TurnAction) -> Ordering {
+        self.bracket
+            .rank()
+            .cmp(&other.bracket.rank())
+            .then(self.speed.cmp(&other.speed))
+    }
+
+    fn reason_before(&self, next: &TurnAction) -> OrderReason {
+        match (self.bracket, next.bracket) {
+            (ActionBracket::Switch, ActionBracket::Move { .. }) => OrderReason::Switch,
+            (
+                ActionBracket::Move { priority },
+                ActionBracket::Move {
+                    priority: next_priority,
+                },
+            ) if priority != next_priority => OrderReason::Priority,
+            _ if self.speed != next.speed => OrderReason::Speed,
+            _ => OrderReason::SpeedTie,
+        }
+    }
+}
+
+pub fn order_actions(actions: &[TurnAction], rng: &mut BattleRng) -> Vec<OrderedAction> {
+    let mut sorted = actions.to_vec();
+    sorted.sort_by(|first, second| second.compare(first));
+
+    //Every group of tied actions gets shuffled, the rng is only used if there actually is a tie.
+    let mut start = 0;
+    while start < sorted.len() {
+        let mut end = start + 1;
+        while end < sorted.len() && sorted[end].compare(&sorted[start]) == Ordering::Equal {
+            end += 1;
+        }
+        shuffle(&mut sorted[start..end], rng);
+        start = end;
+    }
+
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(index, action)| OrderedAction {
+            position: action.position,
+            reason: match sorted.get(index + 1) {
+                Some(next) => action.reason_before(next),
+                None => OrderReason::Last,
+            },
+        })
+        .collect()
+}
+
+fn shuffle(actions: &mut [TurnAction], rng: &mut BattleRng) {
+    for index in (1..actions.len()).rev() {
+        let other = rng.range(0, index as u32) as usize;
+        actions.swap(index, other);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        order::{order_actions, ActionBracket, OrderReason, OrderedAction, TurnAction},
+        rng::BattleRng,
+        Position,
+    };
+
+    fn attack(side: usize, priority: i8, speed: u16) -> TurnAction {
+        TurnAction {
+            position: Position { side, index: 0 },
+            bracket: ActionBracket::Move { priority },
+            speed,
+        }
+    }
+
+    fn switch(side: usize, speed: u16) -> TurnAction {
+        TurnAction {
+            position: Position { side, index: 0 },
+            bracket: ActionBracket::Switch,
+            speed,
+        }
+    }
+
+    fn sides(order: &[OrderedAction]) -> Vec<usize> {
+        order.iter().map(|ordered| ordered.position.side).collect()
+    }
+
+    #[test]
+    fn test_faster_creature_moves_first() {
+        let order = order_actions(
+            &[attack(0, 0, 50), attack(1, 0, 80)],
+            &mut BattleRng::new(1),
+        );
+
+        assert_eq!(
+            order,
+            vec![
+                OrderedAction {
+                    position: Position { side: 1, index: 0 },
+                    reason: OrderReason::Speed
+                },
+                OrderedAction {
+                    position: Position { side: 0, index: 0 },
+                    reason: OrderReason::Last
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_priority_beats_speed() {
+        let order = order_actions(
+            &[attack(0, 1, 10), attack(1, 0, 200)],
+            &mut BattleRng::new(1),
+        );
+
+        assert_eq!(sides(&order), vec![0, 1]);
+        assert_eq!(order[0].reason, OrderReason::Priority);
+    }
+
+    #[test]
+    fn test_negative_priority_moves_last() {
+        let order = order_actions(
+            &[attack(0, -3, 200), attack(1, 0, 10)],
+            &mut BattleRng::new(1),
+        );
+
+        assert_eq!(sides(&order), vec![1, 0]);
+        assert_eq!(order[0].reason, OrderReason::Priority);
+    }
+
+    #[test]
+    fn test_switches_happen_before_priority_moves() {
+        let order = order_actions(&[attack(0, 5, 200), switch(1, 10)], &mut BattleRng::new(1));
+
+        assert_eq!(sides(&order), vec![1, 0]);
+        assert_eq!(order[0].reason, OrderReason::Switch);
+    }
+
+    #[test]
+    fn test_switches_are_ordered_by_speed() {
+        let order = order_actions(&[switch(0, 10), switch(1, 20)], &mut BattleRng::new(1));
+
+        assert_eq!(sides(&order), vec![1, 0]);
+        assert_eq!(order[0].reason, OrderReason::Speed);
+    }
+
+    #[test]
+    fn test_speed_ties_are_broken_by_the_seed() {
+        let actions = [attack(0, 0, 100), attack(1, 0, 100)];
+        let mut first_moved = [0; 2];
+
+        for seed in 0..200 {
+            let order = order_actions(&actions, &mut BattleRng::new(seed));
+            assert_eq!(order[0].reason, OrderReason::SpeedTie);
+            assert_eq!(
+                order,
+                order_actions(&actions, &mut BattleRng::new(seed)),
+                "The same seed has to produce the same order."
+            );
+            first_moved[order[0].position.side] += 1;
+        }
+
+        assert!(first_moved[0] > 50);
+        assert!(first_moved[1] > 50);
+    }
+
+    #[test]
+    fn test_rng_is_untouched_without_ties() {
+        let mut rng = BattleRng::new(7);
+
+        order_actions(&[attack(0, 0, 50), attack(1, 0, 80)], &mut rng);
+
+        assert_eq!(rng.next_u64(), BattleRng::new(7).next_u64());
+    }
+}
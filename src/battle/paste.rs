@@ -0,0 +1,459 @@
+use crate::{
+    battle::{
+        stats::{Nature, Stats},
+        team::CreatureSet,
+    },
+    dex::{moves::BoostableStat, Dex},
+};
+
+//The plain text format players use to share teams, one block per creature:
+//
+//  Nickname (Species) @ Item
+//  Ability: Ability Name
+//  Level: 50
+//  EVs: 252 Atk / 4 SpD / 252 Spe
+//  Jolly Nature
+//  - Move Name
+//
+//Names are turned into ids by lowercasing them and joining the words with dashes.
+
+//The level of creatures whose text leaves it out.
+const DEFAULT_LEVEL: u8 = 100;
+
+//Details other team builders write which don't exist in this game, they are skipped when importing.
+const IGNORED_KEYS: [&str; 8] = [
+    "IVs",
+    "Shiny",
+    "Happiness",
+    "Tera Type",
+    "Dynamax Level",
+    "Gigantamax",
+    "Pokeball",
+    "Hidden Power",
+];
+
+//In the same order as Stats::values.
+const STAT_NAMES: [&str; 6] = ["HP", "Atk", "Def", "SpA", "SpD", "Spe"];
+
+const NATURES: [(&str, BoostableStat, BoostableStat); 25] = {
+    use BoostableStat::*;
+    [
+        ("Hardy", Attack, Attack),
+        ("Lonely", Attack, Defense),
+        ("Brave", Attack, Speed),
+        ("Adamant", Attack, SpecialAttack),
+        ("Naughty", Attack, SpecialDefense),
+        ("Bold", Defense, Attack),
+        ("Docile", Defense, Defense),
+        ("Relaxed", Defense, Speed),
+        ("Impish", Defense, SpecialAttack),
+        ("Lax", Defense, SpecialDefense),
+        ("Timid", Speed, Attack),
+        ("Hasty", Speed, Defense),
+        ("Serious", Speed, Speed),
+        ("Jolly", Speed, SpecialAttack),
+        ("Naive", Speed, SpecialDefense),
+        ("Modest", SpecialAttack, Attack),
+        ("Mild", SpecialAttack, Defense),
+        ("Quiet", SpecialAttack, Speed),
+        ("Bashful", SpecialAttack, SpecialAttack),
+        ("Rash", SpecialAttack, SpecialDefense),
+        ("Calm", SpecialDefense, Attack),
+        ("Gentle", SpecialDefense, Defense),
+        ("Sassy", SpecialDefense, Speed),
+        ("Careful", SpecialDefense, SpecialAttack),
+        ("Quirky", SpecialDefense, SpecialDefense),
+    ]
+};
+
+//Lines and columns start at 1, like in any text editor.
+#[derive(Eq, PartialEq, Debug)]
+pub struct PasteError {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) reason: String,
+}
+
+impl PasteError {
+    pub fn message(&self) -> String {
+        format!(
+            "Line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+struct PasteLine<'a> {
+    number: usize,
+    text: &'a str,
+}
+
+impl PasteLine<'_> {
+    //The part has to be a slice of the line, where it starts is the column of the error.
+    fn error(&self, part: &str, reason: String) -> PasteError {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        PasteError {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            reason,
+        }
+    }
+}
+
+fn to_id(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|char| char.is_alphanumeric() || *char == '-')
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+//Only checks the syntax, whether the species, moves and so on exist is up to the team validation.
+pub fn parse_paste(text: &str) -> Result<Vec<CreatureSet>, PasteError> {
+    let mut sets = Vec::new();
+    let mut current: Option<CreatureSet> = None;
+    for (index, text) in text.lines().enumerate() {
+        let line = PasteLine {
+            number: index + 1,
+            text,
+        };
+        let content = text.trim();
+        if content.is_empty() {
+            sets.extend(current.take());
+            continue;
+        }
+        //Team builders put a header with the name of the team above the creatures.
+        if content.starts_with("===") {
+            continue;
+        }
+        match current.as_mut() {
+            None => current = Some(parse_header(&line, content)?),
+            Some(set) => parse_detail(&line, content, set)?,
+        }
+    }
+    sets.extend(current);
+    Ok(sets)
+}
+
+fn parse_header(line: &PasteLine, content: &str) -> Result<CreatureSet, PasteError> {
+    let (creature, item) = match content.split_once('@') {
+        Some((creature, item)) => (creature.trim(), Some(item.trim())),
+        None => (content, None),
+    };
+    if item == Some("") {
+        return Err(line.error(
+            content,
+            String::from("The @ has to be followed by an item."),
+        ));
+    }
+    //There are no genders in this game, but pastes from elsewhere might still have them.
+    let creature = creature
+        .strip_suffix(" (M)")
+        .or_else(|| creature.strip_suffix(" (F)"))
+        .unwrap_or(creature);
+    let (nickname, species) = match creature
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+    {
+        Some((nickname, species)) => (Some(nickname.trim()), species.trim()),
+        None => (None, creature),
+    };
+    if species.is_empty() || nickname == Some("") {
+        return Err(line.error(
+            creature,
+            String::from(
+                "Expected a species or a nickname followed by the species in parentheses.",
+            ),
+        ));
+    }
+    Ok(CreatureSet {
+        species: to_id(species),
+        nickname: nickname.map(String::from),
+        level: DEFAULT_LEVEL,
+        ability: None,
+        item: item.map(to_id),
+        moves: Vec::new(),
+        investment: Stats::default(),
+        nature: None,
+    })
+}
+
+fn parse_detail(line: &PasteLine, content: &str, set: &mut CreatureSet) -> Result<(), PasteError> {
+    if let Some(move_name) = content.strip_prefix('-') {
+        let move_name = move_name.trim();
+        if move_name.is_empty() {
+            return Err(line.error(content, String::from("The - has to be followed by a move.")));
+        }
+        set.moves.push(to_id(move_name));
+        return Ok(());
+    }
+    if let Some(nature) = content.strip_suffix(" Nature") {
+        let Some((_, raised, lowered)) = NATURES
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(nature.trim()))
+        else {
+            return Err(line.error(content, format!("{} is not a known nature.", nature)));
+        };
+        set.nature = Some(Nature {
+            raised: *raised,
+            lowered: *lowered,
+        });
+        return Ok(());
+    }
+    let Some((key, value)) = content.split_once(':') else {
+        return Err(line.error(
+            content,
+            String::from("Expected an ability, level, EVs, nature or move."),
+        ));
+    };
+    let value = value.trim();
+    match key.trim() {
+        "Ability" if !value.is_empty() => set.ability = Some(to_id(value)),
+        "Ability" => {
+            return Err(line.error(value, String::from("The ability is missing.")));
+        }
+        "Level" => {
+            set.level = value
+                .parse()
+                .map_err(|_| line.error(value, format!("{} is not a valid level.", value)))?;
+        }
+        "EVs" => set.investment = parse_investment(line, value)?,
+        key if IGNORED_KEYS.contains(&key) => {}
+        key => {
+            return Err(line.error(content, format!("{} is not supported.", key)));
+        }
+    }
+    Ok(())
+}
+
+fn parse_investment(line: &PasteLine, value: &str) -> Result<Stats, PasteError> {
+    let mut values = [0; 6];
+    for part in value.split('/') {
+        let part = part.trim();
+        let Some((amount, stat)) = part.split_once(' ') else {
+            return Err(line.error(
+                part,
+                String::from("Expected an amount followed by a stat, like 252 Spe."),
+            ));
+        };
+        let stat = stat.trim();
+        let Some(index) = STAT_NAMES.iter().position(|name| *name == stat) else {
+            return Err(line.error(
+                stat,
+                format!(
+                    "{} is not a stat, use one of {}.",
+                    stat,
+                    STAT_NAMES.join(", ")
+                ),
+            ));
+        };
+        values[index] = amount
+            .parse()
+            .map_err(|_| line.error(amount, format!("{} is not a valid amount.", amount)))?;
+    }
+    let [hp, attack, defense, special_attack, special_defense, speed] = values;
+    Ok(Stats {
+        hp,
+        attack,
+        defense,
+        special_attack,
+        special_defense,
+        speed,
+    })
+}
+
+//Uses the names from the dex, ids which don't exist are written as they are.
+pub fn export_paste(dex: &Dex, sets: &[CreatureSet]) -> String {
+    sets.iter()
+        .map(|set| export_set(dex, set))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn export_set(dex: &Dex, set: &CreatureSet) -> String {
+    let species = dex
+        .species
+        .get(&set.species)
+        .map_or(set.species.as_str(), |species| species.name.as_str());
+    let mut text = match &set.nickname {
+        Some(nickname) => format!("{} ({})", nickname, species),
+        None => String::from(species),
+    };
+    if let Some(item) = &set.item {
+        let name = dex
+            .items
+            .get(item)
+            .map_or(item.as_str(), |item| item.data.name.as_str());
+        text.push_str(&format!(" @ {}", name));
+    }
+    text.push('\n');
+    if let Some(ability) = &set.ability {
+        let name = dex
+            .abilities
+            .get(ability)
+            .map_or(ability.as_str(), |ability| ability.data.name.as_str());
+        text.push_str(&format!("Ability: {}\n", name));
+    }
+    if set.level != DEFAULT_LEVEL {
+        text.push_str(&format!("Level: {}\n", set.level));
+    }
+    let investment: Vec<String> = STAT_NAMES
+        .iter()
+        .zip(set.investment.values())
+        .filter(|(_, invested)| *invested > 0)
+        .map(|(name, invested)| format!("{} {}", invested, name))
+        .collect();
+    if !investment.is_empty() {
+        text.push_str(&format!("EVs: {}\n", investment.join(" / ")));
+    }
+    let nature = set.nature.and_then(|nature| {
+        NATURES
+            .iter()
+            .find(|(_, raised, lowered)| *raised == nature.raised && *lowered == nature.lowered)
+    });
+    if let Some((name, _, _)) = nature {
+        text.push_str(&format!("{} Nature\n", name));
+    }
+    for move_id in &set.moves {
+        let name = dex
+            .moves
+            .get(move_id)
+            .map_or(move_id.as_str(), |known| known.name.as_str());
+        text.push_str(&format!("- {}\n", name));
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        paste::{export_paste, parse_paste, PasteError},
+        stats::{Nature, Stats},
+    };
+    use crate::dex::{moves::BoostableStat, Dex};
+    use crate::DATA_DIRECTORY;
+
+    const PASTE: &str = "=== Rain ===
+
+Bubbles (Tidepup) (F) @ Oran Berry
+Ability: Torrent Heart
+Level: 50
+EVs: 252 HP / 4 Def / 252 Spe
+Jolly Nature
+- Water Gun
+- Aqua Jet
+
+Sproutle
+- Vine Whip
+";
+
+    #[test]
+    fn test_parsing_a_paste() {
+        let sets = parse_paste(PASTE).unwrap();
+
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].species, "tidepup");
+        assert_eq!(sets[0].nickname, Some(String::from("Bubbles")));
+        assert_eq!(sets[0].item, Some(String::from("oran-berry")));
+        assert_eq!(sets[0].ability, Some(String::from("torrent-heart")));
+        assert_eq!(sets[0].level, 50);
+        assert_eq!(
+            sets[0].investment,
+            Stats {
+                hp: 252,
+                attack: 0,
+                defense: 4,
+                special_attack: 0,
+                special_defense: 0,
+                speed: 252,
+            }
+        );
+        assert_eq!(
+            sets[0].nature,
+            Some(Nature {
+                raised: BoostableStat::Speed,
+                lowered: BoostableStat::SpecialAttack
+            })
+        );
+        assert_eq!(sets[0].moves, vec!["water-gun", "aqua-jet"]);
+        assert_eq!(sets[1].species, "sproutle");
+        assert_eq!(sets[1].level, 100);
+        assert_eq!(sets[1].moves, vec!["vine-whip"]);
+    }
+
+    #[test]
+    fn test_exported_pastes_can_be_imported_again() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let sets = parse_paste(PASTE).unwrap();
+
+        let exported = export_paste(&dex, &sets);
+
+        assert_eq!(
+            exported,
+            "Bubbles (Tidepup) @ Oran Berry
+Ability: Torrent Heart
+Level: 50
+EVs: 252 HP / 4 Def / 252 Spe
+Jolly Nature
+- Water Gun
+- Aqua Jet
+
+Sproutle
+- Vine Whip
+"
+        );
+        assert_eq!(parse_paste(&exported).unwrap(), sets);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_problem() {
+        let position = |paste: &str| {
+            parse_paste(paste)
+                .map(|_| ())
+                .map_err(|PasteError { line, column, .. }| (line, column))
+        };
+
+        assert_eq!(position("Tidepup\nEVs: 252 HP / 4 Atack"), Err((2, 17)));
+        assert_eq!(position("Tidepup\nEVs: 252 HP / lots Atk"), Err((2, 15)));
+        assert_eq!(position("Tidepup\n  Level: fifty"), Err((2, 10)));
+        assert_eq!(position("Tidepup\nSleepy Nature"), Err((2, 1)));
+        assert_eq!(position("Tidepup\n- Tackle\nSparkly: Yes"), Err((3, 1)));
+        assert_eq!(position("Tidepup @"), Err((1, 1)));
+    }
+
+    #[test]
+    fn test_details_of_other_team_builders_are_skipped() {
+        //Exported as is from another team builder, including the trailing spaces.
+        let paste = "=== [gen9] Rain ===
+
+Bubbles (Tidepup) (M) @ Oran Berry  
+Ability: Torrent Heart  
+Level: 50  
+Shiny: Yes  
+Happiness: 0  
+Tera Type: Water  
+EVs: 252 HP / 4 Def / 252 Spe  
+Jolly Nature  
+IVs: 0 Atk  
+- Water Gun  
+- Aqua Jet  
+
+Sproutle (F)  
+Ability: Overgrowth  
+Tera Type: Grass  
+- Vine Whip  
+";
+
+        let sets = parse_paste(paste).unwrap();
+
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0], parse_paste(PASTE).unwrap()[0]);
+        assert_eq!(sets[1].species, "sproutle");
+        assert_eq!(sets[1].ability, Some(String::from("overgrowth")));
+        assert_eq!(sets[1].moves, vec!["vine-whip"]);
+    }
+}
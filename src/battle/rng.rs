@@ -0,0 +1,105 @@
+//A tiny SplitMix64 generator. It is implemented here instead of pulling in an external crate,
+//because replays depend on the exact sequence of numbers never changing between versions.
+#[derive(Debug, Clone)]
+pub struct BattleRng {
+    state: u64,
+}
+
+impl BattleRng {
+    pub fn new(seed: u64) -> Self {
+        BattleRng { state: seed }
+    }
+
+    //Every turn gets its own generator, so nothing but the seed has to be persisted.
+    pub fn for_turn(seed: u32, turn: u32) -> Self {
+        Self::new(((seed as u64) << 32) | turn as u64)
+    }
+
+    //Replacements happen between turns and get a sequence which doesn't overlap with the turn's.
+    pub fn for_replacements(seed: u32, turn: u32) -> Self {
+        Self::new(!(((seed as u64) << 32) | turn as u64))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    //Returns a number between min and max, both inclusive.
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        if max <= min {
+            return min;
+        }
+        min + (self.next_u64() % (max - min + 1) as u64) as u32
+    }
+
+    pub fn chance(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.range(1, denominator) <= numerator
+    }
+
+    //Certain outcomes don't advance the generator, so adding an effect which always or never
+    //happens doesn't shift every roll after it.
+    pub fn percent(&mut self, chance: u8) -> bool {
+        match chance {
+            0 => false,
+            100.. => true,
+            _ => self.chance(chance as u32, 100),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::rng::BattleRng;
+
+    #[test]
+    fn test_same_seed_produces_same_sequence() {
+        let mut first = BattleRng::new(42);
+        let mut second = BattleRng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_turns_produce_different_sequences() {
+        let mut first = BattleRng::for_turn(42, 1);
+        let mut second = BattleRng::for_turn(42, 2);
+
+        assert_ne!(first.next_u64(), second.next_u64());
+    }
+
+    #[test]
+    fn test_certain_percentages_dont_use_the_generator() {
+        let mut rng = BattleRng::new(3);
+
+        assert!(rng.percent(100));
+        assert!(!rng.percent(0));
+        assert_eq!(rng.next_u64(), BattleRng::new(3).next_u64());
+    }
+
+    #[test]
+    fn test_range_stays_in_bounds() {
+        let mut rng = BattleRng::new(7);
+
+        for _ in 0..1000 {
+            let value = rng.range(85, 100);
+            assert!((85..=100).contains(&value));
+        }
+        assert_eq!(rng.range(5, 5), 5);
+    }
+
+    #[test]
+    fn test_chance_extremes() {
+        let mut rng = BattleRng::new(7);
+
+        for _ in 0..100 {
+            assert!(rng.chance(100, 100));
+            assert!(!rng.chance(0, 100));
+        }
+    }
+}
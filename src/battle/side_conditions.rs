@@ -0,0 +1,442 @@
+use crate::battle::{Battle, BattleEvent, Position};
+use crate::dex::{moves::MoveCategory, side_conditions::SideConditionKind, Dex};
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ActiveSideCondition {
+    pub(crate) kind: SideConditionKind,
+    pub(crate) layers: u8,
+    //Only set for conditions which wear off on their own.
+    pub(crate) turns_left: Option<u8>,
+}
+
+impl Battle {
+    //Adds a layer to a condition which is already up, fails once it can't be stacked any further.
+    pub(super) fn add_side_condition(
+        &mut self,
+        dex: &Dex,
+        side: usize,
+        kind: SideConditionKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let data = dex.side_conditions.get(kind);
+        let conditions = &mut self.sides[side].conditions;
+        match conditions.iter_mut().find(|active| active.kind == kind) {
+            Some(active) if active.layers >= data.max_layers() => return false,
+            Some(active) => active.layers += 1,
+            None => conditions.push(ActiveSideCondition {
+                kind,
+                layers: 1,
+                turns_left: data.duration,
+            }),
+        }
+        events.push(BattleEvent::SideConditionStarted {
+            side,
+            condition: kind,
+        });
+        true
+    }
+
+    pub(super) fn clear_side_conditions(
+        &mut self,
+        side: usize,
+        kinds: &[SideConditionKind],
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let mut cleared = false;
+        self.sides[side].conditions.retain(|active| {
+            if !kinds.contains(&active.kind) {
+                return true;
+            }
+            events.push(BattleEvent::SideConditionEnded {
+                side,
+                condition: active.kind,
+            });
+            cleared = true;
+            false
+        });
+        cleared
+    }
+
+    pub(super) fn screen_modifier(&self, dex: &Dex, side: usize, category: MoveCategory) -> f32 {
+        self.sides[side]
+            .conditions
+            .iter()
+            .filter_map(|active| dex.side_conditions.get(active.kind).screen)
+            .filter(|screen| screen.category == category)
+            .map(|screen| screen.multiplier)
+            .product()
+    }
+
+    //Hurts the creature which just entered the position for every hazard on its side.
+    pub(super) fn entry_hazards(
+        &mut self,
+        dex: &Dex,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let Some(species) = dex.species.get(&self.creature(position).species) else {
+            return;
+        };
+        for hazard in self.sides[position.side].conditions.clone() {
+            let data = dex.side_conditions.get(hazard.kind);
+            let Some(damage) = data.entry_damage.get(hazard.layers as usize - 1) else {
+                continue;
+            };
+            let creature = self.creature_mut(position);
+            let immune = species
+                .types
+                .iter()
+                .any(|type_id| data.immune_types.contains(type_id));
+            if creature.is_fainted() || immune {
+                continue;
+            }
+            let effectiveness = data.entry_damage_type.as_ref().map_or(1.0, |type_id| {
+                dex.types.effectiveness(type_id, &species.types)
+            });
+            let amount = (damage.of(creature.stats.hp) as f32 * effectiveness) as u16;
+            if amount == 0 {
+                continue;
+            }
+            let dealt = creature.take_damage(amount);
+            events.push(BattleEvent::HurtByHazard {
+                position,
+                condition: hazard.kind,
+                amount: dealt,
+                remaining_hp: creature.current_hp,
+            });
+        }
+        self.check_fainted(position, events);
+    }
+
+    pub(super) fn side_conditions_countdown(&mut self, events: &mut Vec<BattleEvent>) {
+        for (side, own_side) in self.sides.iter_mut().enumerate() {
+            own_side.conditions.retain_mut(|active| {
+                let Some(turns_left) = &mut active.turns_left else {
+                    return true;
+                };
+                *turns_left = turns_left.saturating_sub(1);
+                if *turns_left > 0 {
+                    return true;
+                }
+                events.push(BattleEvent::SideConditionEnded {
+                    side,
+                    condition: active.kind,
+                });
+                false
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        side_conditions::ActiveSideCondition,
+        stats::Stats,
+        Action, Battle, BattleEvent, BattleFormat, BattlePhase, Position,
+    };
+    use crate::dex::{side_conditions::SideConditionKind, Dex};
+    use crate::DATA_DIRECTORY;
+
+    fn creature(species: &str, moves: &[&str]) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            moves
+                .iter()
+                .map(|id| BattleMove {
+                    id: String::from(*id),
+                    pp: 35,
+                    revealed: false,
+                })
+                .collect(),
+        )
+    }
+
+    fn hazard(kind: SideConditionKind, layers: u8) -> ActiveSideCondition {
+        ActiveSideCondition {
+            kind,
+            layers,
+            turns_left: None,
+        }
+    }
+
+    #[test]
+    fn test_hazards_stack_up_to_their_limit() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", &["spikes"])],
+                vec![creature("tidepup", &["sharpen-claws"])],
+            ],
+            1,
+        );
+        let mut events = Vec::new();
+
+        for _ in 0..3 {
+            assert!(battle.add_side_condition(&dex, 1, SideConditionKind::Spikes, &mut events));
+        }
+
+        assert!(!battle.add_side_condition(&dex, 1, SideConditionKind::Spikes, &mut events));
+        assert_eq!(
+            battle.sides[1].conditions,
+            vec![hazard(SideConditionKind::Spikes, 3)]
+        );
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_hazards_hurt_creatures_switching_in() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", &["sharpen-claws"])],
+                vec![
+                    creature("tidepup", &["sharpen-claws"]),
+                    creature("cindercub", &["sharpen-claws"]),
+                    creature("plumbird", &["sharpen-claws"]),
+                ],
+            ],
+            1,
+        );
+        battle.sides[1].conditions = vec![
+            hazard(SideConditionKind::Spikes, 1),
+            hazard(SideConditionKind::StealthRock, 1),
+        ];
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 1 })
+            .unwrap()
+            .unwrap();
+
+        //Rocks are super effective against fire, so the cub takes twice the damage from them.
+        assert!(events.contains(&BattleEvent::HurtByHazard {
+            position: Position { side: 1, index: 0 },
+            condition: SideConditionKind::Spikes,
+            amount: 20,
+            remaining_hp: 140
+        }));
+        assert!(events.contains(&BattleEvent::HurtByHazard {
+            position: Position { side: 1, index: 0 },
+            condition: SideConditionKind::StealthRock,
+            amount: 40,
+            remaining_hp: 100
+        }));
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 2 })
+            .unwrap()
+            .unwrap();
+
+        //Flying creatures avoid the spikes, but not the rocks.
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| matches!(event, BattleEvent::HurtByHazard { .. }))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_fainting_from_hazards_requires_another_replacement() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut weakened = creature("cindercub", &["sharpen-claws"]);
+        weakened.current_hp = 10;
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", &["sharpen-claws"])],
+                vec![
+                    creature("tidepup", &["sharpen-claws"]),
+                    weakened,
+                    creature("plumbird", &["sharpen-claws"]),
+                ],
+            ],
+            1,
+        );
+        battle.sides[1].conditions = vec![hazard(SideConditionKind::StealthRock, 1)];
+        battle.sides[1].team[0].current_hp = 0;
+        battle.sides[1].needs_replacement = vec![0];
+        battle.phase = BattlePhase::WaitingForReplacement;
+
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 1 })
+            .unwrap()
+            .unwrap();
+
+        assert!(events.contains(&BattleEvent::WaitingForReplacement {
+            position: Position { side: 1, index: 0 }
+        }));
+        assert_eq!(battle.phase, BattlePhase::WaitingForReplacement);
+        assert_eq!(battle.sides[1].needs_replacement, vec![0]);
+    }
+
+    #[test]
+    fn test_screens_reduce_damage_and_wear_off() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut remaining_hp = Vec::new();
+
+        for move_id in ["sharpen-claws", "reflect"] {
+            let mut battle = Battle::new(
+                BattleFormat::Singles,
+                [
+                    vec![creature("tidepup", &["tackle"])],
+                    vec![creature("mindmoth", &[move_id])],
+                ],
+                5,
+            );
+            battle
+                .submit_action(
+                    &dex,
+                    0,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap();
+            battle
+                .submit_action(
+                    &dex,
+                    1,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap();
+            remaining_hp.push(battle.sides[1].creature_at(0).current_hp);
+        }
+
+        assert!(remaining_hp[1] > remaining_hp[0]);
+    }
+
+    #[test]
+    fn test_screens_end_after_their_duration() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", &["sharpen-claws"])],
+                vec![creature("mindmoth", &["light-screen"])],
+            ],
+            1,
+        );
+        let mut last = Vec::new();
+
+        for _ in 0..5 {
+            battle
+                .submit_action(
+                    &dex,
+                    0,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap();
+            last = battle
+                .submit_action(
+                    &dex,
+                    1,
+                    0,
+                    Action::Move {
+                        slot: 0,
+                        target: None,
+                    },
+                )
+                .unwrap()
+                .unwrap();
+        }
+
+        assert_eq!(
+            last.last(),
+            Some(&BattleEvent::SideConditionEnded {
+                side: 1,
+                condition: SideConditionKind::LightScreen
+            })
+        );
+    }
+
+    #[test]
+    fn test_moves_clear_side_conditions() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("tidepup", &["rapid-spin"])],
+                vec![creature("mindmoth", &["sharpen-claws"])],
+            ],
+            1,
+        );
+        battle.sides[0].conditions = vec![hazard(SideConditionKind::Spikes, 2)];
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        assert!(events.contains(&BattleEvent::SideConditionEnded {
+            side: 0,
+            condition: SideConditionKind::Spikes
+        }));
+        assert!(battle.sides[0].conditions.is_empty());
+    }
+}
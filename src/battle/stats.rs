@@ -0,0 +1,149 @@
+use crate::dex::moves::BoostableStat;
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct Stats {
+    pub(crate) hp: u16,
+    pub(crate) attack: u16,
+    pub(crate) defense: u16,
+    pub(crate) special_attack: u16,
+    pub(crate) special_defense: u16,
+    pub(crate) speed: u16,
+}
+
+//Raises one stat by a tenth and lowers another one by the same amount. Health is never affected.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Nature {
+    pub(crate) raised: BoostableStat,
+    pub(crate) lowered: BoostableStat,
+}
+
+impl Nature {
+    //Accuracy and evasion only change through stages in a battle, never through a nature.
+    pub fn is_valid(&self) -> bool {
+        [self.raised, self.lowered]
+            .iter()
+            .all(|stat| !matches!(stat, BoostableStat::Accuracy | BoostableStat::Evasion))
+    }
+
+    pub fn multiplier(&self, stat: BoostableStat) -> f32 {
+        if self.raised == self.lowered {
+            1.0
+        } else if stat == self.raised {
+            1.1
+        } else if stat == self.lowered {
+            0.9
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Stats {
+    //There are no individual values in this game, every creature is calculated as if it had perfect ones.
+    const INDIVIDUAL_VALUE: u32 = 31;
+
+    //Every 4 points invested into a stat raise it by one point at level 100.
+    pub fn at_level(base: &Stats, level: u8, investment: &Stats, nature: Option<Nature>) -> Stats {
+        let level = level as u32;
+        let scale = |base: u16, invested: u16| {
+            (2 * base as u32 + Self::INDIVIDUAL_VALUE + invested as u32 / 4) * level / 100
+        };
+        let other = |base: u16, invested: u16, stat: BoostableStat| {
+            let multiplier = nature.map_or(1.0, |nature| nature.multiplier(stat));
+            ((scale(base, invested) + 5) as f32 * multiplier) as u16
+        };
+        Stats {
+            hp: (scale(base.hp, investment.hp) + level + 10) as u16,
+            attack: other(base.attack, investment.attack, BoostableStat::Attack),
+            defense: other(base.defense, investment.defense, BoostableStat::Defense),
+            special_attack: other(
+                base.special_attack,
+                investment.special_attack,
+                BoostableStat::SpecialAttack,
+            ),
+            special_defense: other(
+                base.special_defense,
+                investment.special_defense,
+                BoostableStat::SpecialDefense,
+            ),
+            speed: other(base.speed, investment.speed, BoostableStat::Speed),
+        }
+    }
+
+    pub fn values(&self) -> [u16; 6] {
+        [
+            self.hp,
+            self.attack,
+            self.defense,
+            self.special_attack,
+            self.special_defense,
+            self.speed,
+        ]
+    }
+
+    //Accuracy and evasion are not real stats, they only ever exist as modifiers.
+    pub fn get(&self, stat: BoostableStat) -> u16 {
+        match stat {
+            BoostableStat::Attack => self.attack,
+            BoostableStat::Defense => self.defense,
+            BoostableStat::SpecialAttack => self.special_attack,
+            BoostableStat::SpecialDefense => self.special_defense,
+            BoostableStat::Speed => self.speed,
+            BoostableStat::Accuracy | BoostableStat::Evasion => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::stats::{Nature, Stats};
+    use crate::dex::moves::BoostableStat;
+
+    #[test]
+    fn test_stats_at_level() {
+        let base = Stats {
+            hp: 100,
+            attack: 100,
+            defense: 50,
+            special_attack: 50,
+            special_defense: 50,
+            speed: 100,
+        };
+
+        let stats = Stats::at_level(&base, 50, &Stats::default(), None);
+
+        assert_eq!(stats.hp, 175);
+        assert_eq!(stats.attack, 120);
+        assert_eq!(stats.defense, 70);
+        assert_eq!(stats.speed, 120);
+    }
+
+    #[test]
+    fn test_investment_and_nature() {
+        let base = Stats {
+            hp: 100,
+            attack: 100,
+            defense: 50,
+            special_attack: 50,
+            special_defense: 50,
+            speed: 100,
+        };
+        let investment = Stats {
+            hp: 252,
+            speed: 252,
+            ..Stats::default()
+        };
+        let nature = Nature {
+            raised: BoostableStat::Speed,
+            lowered: BoostableStat::SpecialAttack,
+        };
+
+        let stats = Stats::at_level(&base, 50, &investment, Some(nature));
+
+        assert_eq!(stats.hp, 207);
+        assert_eq!(stats.attack, 120);
+        assert_eq!(stats.special_attack, 63);
+        assert_eq!(stats.speed, 167);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::battle::{rng::BattleRng, Battle, BattleEvent, Position};
+use crate::dex::{moves::StatusKind, Dex};
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Status {
+    pub(crate) kind: StatusKind,
+    //Only set for statuses with a duration, counts the turns the creature still can't act.
+    pub(crate) turns_left: Option<u8>,
+}
+
+impl Battle {
+    //A creature only ever has one major status, trying to apply a second one does nothing.
+    pub(super) fn apply_status(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        kind: StatusKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let condition = dex.statuses.get(kind);
+        let creature = self.creature(position);
+        if creature.is_fainted() || creature.status.is_some() {
+            return false;
+        }
+        let immune = dex.species.get(&creature.species).is_some_and(|species| {
+            species
+                .types
+                .iter()
+                .any(|type_id| condition.immune_types.contains(type_id))
+        });
+        if immune {
+            return false;
+        }
+        if let Some(terrain) = self.terrain_blocking_status(dex, kind) {
+            events.push(BattleEvent::PreventedByTerrain { position, terrain });
+            return false;
+        }
+        if !self.status_allowed(dex, rng, position, kind, events) {
+            return false;
+        }
+        self.creature_mut(position).status = Some(Status {
+            kind,
+            turns_left: condition
+                .duration
+                .map(|duration| rng.range(duration.min as u32, duration.max as u32) as u8),
+        });
+        events.push(BattleEvent::StatusApplied {
+            position,
+            status: kind,
+        });
+        true
+    }
+
+    //Called right before a creature uses a move.
+    pub(super) fn can_act(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let Some(mut status) = self.creature(position).status else {
+            return true;
+        };
+        let condition = dex.statuses.get(status.kind);
+        if let Some(turns_left) = status.turns_left {
+            if turns_left == 0 {
+                self.cure_status(position, events);
+                return true;
+            }
+            status.turns_left = Some(turns_left - 1);
+            self.creature_mut(position).status = Some(status);
+        }
+        if rng.percent(condition.cure_chance) {
+            self.cure_status(position, events);
+            return true;
+        }
+        if rng.percent(condition.skip_chance) {
+            events.push(BattleEvent::PreventedByStatus {
+                position,
+                status: status.kind,
+            });
+            return false;
+        }
+        true
+    }
+
+    pub(super) fn cure_status(&mut self, position: Position, events: &mut Vec<BattleEvent>) {
+        if let Some(status) = self.creature_mut(position).status.take() {
+            events.push(BattleEvent::StatusCured {
+                position,
+                status: status.kind,
+            });
+        }
+    }
+
+    //Some statuses are cured by being hit with a specific type, like thawing out from fire.
+    pub(super) fn cure_status_by_hit(
+        &mut self,
+        dex: &Dex,
+        position: Position,
+        move_type: &str,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let Some(status) = self.creature(position).status else {
+            return;
+        };
+        let condition = dex.statuses.get(status.kind);
+        if condition
+            .cured_by_move_types
+            .iter()
+            .any(|type_id| type_id == move_type)
+        {
+            self.cure_status(position, events);
+        }
+    }
+
+    pub(super) fn status_damage(
+        &mut self,
+        dex: &Dex,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let creature = self.creature_mut(position);
+        let Some(status) = creature.status else {
+            return;
+        };
+        if creature.is_fainted() {
+            return;
+        }
+        let Some(damage) = dex.statuses.get(status.kind).end_of_turn_damage else {
+            return;
+        };
+        let dealt = creature.take_damage(damage.of(creature.stats.hp));
+        events.push(BattleEvent::HurtByStatus {
+            position,
+            status: status.kind,
+            amount: dealt,
+            remaining_hp: creature.current_hp,
+        });
+        self.check_fainted(position, events);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        rng::BattleRng,
+        stats::Stats,
+        status::Status,
+        Action, Battle, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::{
+        moves::{BoostableStat, StatusKind},
+        Dex,
+    };
+    use crate::DATA_DIRECTORY;
+
+    fn creature(species: &str, move_id: &str) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        )
+    }
+
+    fn battle(first: BattleCreature, second: BattleCreature) -> Battle {
+        Battle::new(BattleFormat::Singles, [vec![first], vec![second]], 1)
+    }
+
+    fn play_turn(battle: &mut Battle, dex: &Dex) -> Vec<BattleEvent> {
+        battle
+            .submit_action(
+                dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_only_one_major_status_at_a_time() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(
+            creature("tidepup", "tackle"),
+            creature("sproutle", "tackle"),
+        );
+        let mut rng = BattleRng::new(1);
+        let mut events = Vec::new();
+
+        assert!(battle.apply_status(
+            &dex,
+            &mut rng,
+            Position { side: 1, index: 0 },
+            StatusKind::Burn,
+            &mut events
+        ));
+        assert!(!battle.apply_status(
+            &dex,
+            &mut rng,
+            Position { side: 1, index: 0 },
+            StatusKind::Poison,
+            &mut events
+        ));
+
+        assert_eq!(
+            battle.sides[1].creature_at(0).status.unwrap().kind,
+            StatusKind::Burn
+        );
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_types_can_be_immune_to_a_status() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(
+            creature("tidepup", "tackle"),
+            creature("cindercub", "tackle"),
+        );
+
+        let applied = battle.apply_status(
+            &dex,
+            &mut BattleRng::new(1),
+            Position { side: 1, index: 0 },
+            StatusKind::Burn,
+            &mut Vec::new(),
+        );
+
+        assert!(!applied);
+        assert_eq!(battle.sides[1].creature_at(0).status, None);
+    }
+
+    #[test]
+    fn test_poison_deals_damage_at_the_end_of_the_turn() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut poisoned = creature("sproutle", "scorch-haze");
+        poisoned.status = Some(Status {
+            kind: StatusKind::Poison,
+            turns_left: None,
+        });
+        let mut battle = battle(creature("tidepup", "scorch-haze"), poisoned);
+
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::HurtByStatus {
+            position: Position { side: 1, index: 0 },
+            status: StatusKind::Poison,
+            amount: 20,
+            remaining_hp: 140,
+        }));
+    }
+
+    #[test]
+    fn test_burn_halves_attack() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut burned = creature("tidepup", "tackle");
+
+        assert_eq!(burned.effective_stat(&dex, BoostableStat::Attack), 100);
+        burned.status = Some(Status {
+            kind: StatusKind::Burn,
+            turns_left: None,
+        });
+        assert_eq!(burned.effective_stat(&dex, BoostableStat::Attack), 50);
+    }
+
+    #[test]
+    fn test_sleeping_creatures_wake_up_after_their_turns_run_out() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut sleeping = creature("tidepup", "tackle");
+        sleeping.status = Some(Status {
+            kind: StatusKind::Sleep,
+            turns_left: Some(2),
+        });
+        let mut battle = battle(sleeping, creature("sproutle", "scorch-haze"));
+
+        for remaining in [1, 0] {
+            let events = play_turn(&mut battle, &dex);
+            assert!(events.contains(&BattleEvent::PreventedByStatus {
+                position: Position { side: 0, index: 0 },
+                status: StatusKind::Sleep
+            }));
+            assert_eq!(
+                battle.sides[0].creature_at(0).status.unwrap().turns_left,
+                Some(remaining)
+            );
+        }
+        let events = play_turn(&mut battle, &dex);
+
+        assert!(events.contains(&BattleEvent::StatusCured {
+            position: Position { side: 0, index: 0 },
+            status: StatusKind::Sleep
+        }));
+        assert!(events.contains(&BattleEvent::UsedMove {
+            position: Position { side: 0, index: 0 },
+            move_id: String::from("tackle")
+        }));
+    }
+
+    #[test]
+    fn test_fire_moves_thaw_frozen_creatures() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut frozen = creature("sproutle", "tackle");
+        frozen.status = Some(Status {
+            kind: StatusKind::Freeze,
+            turns_left: None,
+        });
+        let mut battle = battle(creature("tidepup", "ember"), frozen);
+        let mut events = Vec::new();
+
+        battle.cure_status_by_hit(&dex, Position { side: 1, index: 0 }, "water", &mut events);
+        assert!(battle.sides[1].creature_at(0).status.is_some());
+        battle.cure_status_by_hit(&dex, Position { side: 1, index: 0 }, "fire", &mut events);
+
+        assert_eq!(battle.sides[1].creature_at(0).status, None);
+        assert_eq!(
+            events,
+            vec![BattleEvent::StatusCured {
+                position: Position { side: 1, index: 0 },
+                status: StatusKind::Freeze
+            }]
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use crate::battle::{volatile::VolatileKind, Battle, BattleError, Position};
+use crate::dex::moves::MoveTarget;
+
+//Moves hitting more than one creature at once deal less damage to each of them.
+pub const SPREAD_MODIFIER: f32 = 0.75;
+
+pub fn spread_modifier(targets: usize) -> f32 {
+    if targets > 1 {
+        SPREAD_MODIFIER
+    } else {
+        1.0
+    }
+}
+
+impl Battle {
+    //Only moves hitting a single other creature can be aimed, every other move hits a fixed set of creatures.
+    pub(super) fn validate_target(
+        &self,
+        position: Position,
+        move_target: MoveTarget,
+        target: Option<Position>,
+    ) -> Result<(), BattleError> {
+        if move_target == MoveTarget::SingleAlly && self.allies(position).is_empty() {
+            return Err(BattleError::InvalidAction(String::from(
+                "There is no ally to target.",
+            )));
+        }
+        let Some(target) = target else {
+            return Ok(());
+        };
+        let valid = match move_target {
+            MoveTarget::SingleFoe => self.foes(position).contains(&target),
+            MoveTarget::SingleAlly => self.allies(position).contains(&target),
+            _ => false,
+        };
+        if !valid {
+            return Err(BattleError::InvalidAction(String::from(
+                "The move can't target that position.",
+            )));
+        }
+        Ok(())
+    }
+
+    //Everything the move is going to hit, fainted creatures are never hit.
+    pub(super) fn move_targets(
+        &self,
+        position: Position,
+        move_target: MoveTarget,
+        chosen: Option<Position>,
+    ) -> Vec<Position> {
+        let living = |targets: Vec<Position>| -> Vec<Position> {
+            targets
+                .into_iter()
+                .filter(|target| !self.creature(*target).is_fainted())
+                .collect()
+        };
+        match move_target {
+            MoveTarget::User => vec![position],
+            MoveTarget::SingleFoe => self.single_foe(position, chosen).into_iter().collect(),
+            MoveTarget::SingleAlly => {
+                let allies = living(self.allies(position));
+                let target = chosen
+                    .filter(|chosen| allies.contains(chosen))
+                    .or(allies.first().copied());
+                target.into_iter().collect()
+            }
+            MoveTarget::AllFoes => living(self.foes(position)),
+            MoveTarget::AllAdjacent => living(
+                self.positions()
+                    .into_iter()
+                    .filter(|other| *other != position)
+                    .collect(),
+            ),
+        }
+    }
+
+    //A foe drawing attention takes the move no matter which foe was chosen. Otherwise the chosen
+    //foe gets hit, falling back to the one across if nothing was chosen or the chosen one fainted.
+    fn single_foe(&self, position: Position, chosen: Option<Position>) -> Option<Position> {
+        let foes: Vec<Position> = self
+            .foes(position)
+            .into_iter()
+            .filter(|foe| !self.creature(*foe).is_fainted())
+            .collect();
+        if let Some(redirected) = foes
+            .iter()
+            .find(|foe| self.has_volatile(**foe, VolatileKind::CenterOfAttention))
+        {
+            return Some(*redirected);
+        }
+        chosen
+            .filter(|chosen| foes.contains(chosen))
+            .or_else(|| foes.iter().find(|foe| foe.index == position.index).copied())
+            .or_else(|| foes.first().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        stats::Stats,
+        volatile::VolatileKind,
+        Action, Battle, BattleError, BattleEvent, BattleFormat, BattlePhase, Position,
+    };
+    use crate::dex::Dex;
+    use crate::DATA_DIRECTORY;
+
+    fn creature(species: &str, speed: u16, move_id: &str) -> BattleCreature {
+        BattleCreature::new(
+            species,
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed,
+            },
+            vec![BattleMove {
+                id: String::from(move_id),
+                pp: 35,
+                revealed: false,
+            }],
+        )
+    }
+
+    fn at(side: usize, index: usize) -> Position {
+        Position { side, index }
+    }
+
+    fn attack(target: Option<Position>) -> Action {
+        Action::Move { slot: 0, target }
+    }
+
+    //Submits the first move of every creature on the field, with the given target for the first one.
+    fn play_turn(battle: &mut Battle, dex: &Dex, target: Option<Position>) -> Vec<BattleEvent> {
+        battle.submit_action(dex, 0, 0, attack(target)).unwrap();
+        battle.submit_action(dex, 0, 1, attack(None)).unwrap();
+        battle.submit_action(dex, 1, 0, attack(None)).unwrap();
+        battle
+            .submit_action(dex, 1, 1, attack(None))
+            .unwrap()
+            .unwrap()
+    }
+
+    fn damaged(events: &[BattleEvent]) -> Vec<Position> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                BattleEvent::Damaged { position, .. } => Some(*position),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_single_target_moves_hit_the_chosen_foe() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Doubles,
+            [
+                vec![
+                    creature("cindercub", 100, "tackle"),
+                    creature("tidepup", 90, "sharpen-claws"),
+                ],
+                vec![
+                    creature("sproutle", 80, "sharpen-claws"),
+                    creature("voltmouse", 70, "sharpen-claws"),
+                ],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex, Some(at(1, 1)));
+
+        assert_eq!(damaged(&events), vec![at(1, 1)]);
+        assert_eq!(battle.creature(at(1, 0)).current_hp, 160);
+    }
+
+    #[test]
+    fn test_spread_moves_hit_every_foe_for_less_damage() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Doubles,
+            [
+                vec![
+                    creature("pebblon", 100, "earthquake"),
+                    creature("plumbird", 90, "sharpen-claws"),
+                ],
+                vec![
+                    creature("voltmouse", 80, "sharpen-claws"),
+                    creature("voltmouse", 70, "sharpen-claws"),
+                ],
+            ],
+            1,
+        );
+        let mut single = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("pebblon", 100, "earthquake")],
+                vec![creature("voltmouse", 80, "sharpen-claws")],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex, None);
+        single.submit_action(&dex, 0, 0, attack(None)).unwrap();
+        single.submit_action(&dex, 1, 0, attack(None)).unwrap();
+
+        //The flying ally is immune, so it takes no damage but still gets hit.
+        assert_eq!(damaged(&events), vec![at(0, 1), at(1, 0), at(1, 1)]);
+        assert_eq!(battle.creature(at(0, 1)).current_hp, 160);
+        assert!(battle.creature(at(1, 0)).current_hp > single.creature(at(1, 0)).current_hp);
+    }
+
+    #[test]
+    fn test_redirection_draws_single_target_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Doubles,
+            [
+                vec![
+                    creature("cindercub", 100, "tackle"),
+                    creature("tidepup", 90, "sharpen-claws"),
+                ],
+                vec![
+                    creature("sproutle", 80, "sharpen-claws"),
+                    creature("plumbird", 10, "follow-me"),
+                ],
+            ],
+            1,
+        );
+
+        let events = play_turn(&mut battle, &dex, Some(at(1, 0)));
+
+        assert_eq!(damaged(&events), vec![at(1, 1)]);
+        assert!(events.contains(&BattleEvent::VolatileEnded {
+            position: at(1, 1),
+            volatile: VolatileKind::CenterOfAttention
+        }));
+    }
+
+    #[test]
+    fn test_ally_moves_need_an_ally() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut doubles = Battle::new(
+            BattleFormat::Doubles,
+            [
+                vec![
+                    creature("gloomwisp", 100, "aromatic-mist"),
+                    creature("tidepup", 90, "sharpen-claws"),
+                ],
+                vec![
+                    creature("sproutle", 80, "sharpen-claws"),
+                    creature("voltmouse", 70, "sharpen-claws"),
+                ],
+            ],
+            1,
+        );
+        let mut singles = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature("gloomwisp", 100, "aromatic-mist")],
+                vec![creature("sproutle", 80, "sharpen-claws")],
+            ],
+            1,
+        );
+
+        assert!(matches!(
+            doubles.submit_action(&dex, 0, 0, attack(Some(at(1, 0)))),
+            Err(BattleError::InvalidAction(_))
+        ));
+        play_turn(&mut doubles, &dex, Some(at(0, 1)));
+
+        assert_eq!(doubles.creature(at(0, 1)).stages.special_defense, 1);
+        assert!(matches!(
+            singles.submit_action(&dex, 0, 0, attack(None)),
+            Err(BattleError::InvalidAction(_))
+        ));
+    }
+
+    #[test]
+    fn test_only_positions_with_a_bench_get_replaced() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Doubles,
+            [
+                vec![
+                    creature("cindercub", 100, "sharpen-claws"),
+                    creature("tidepup", 90, "sharpen-claws"),
+                    creature("sproutle", 80, "sharpen-claws"),
+                ],
+                vec![
+                    creature("voltmouse", 70, "sharpen-claws"),
+                    creature("pebblon", 60, "sharpen-claws"),
+                ],
+            ],
+            1,
+        );
+        for position in [at(0, 0), at(0, 1), at(1, 0)] {
+            battle.creature_mut(position).current_hp = 0;
+        }
+        let mut events = Vec::new();
+
+        battle.request_replacements(&mut events);
+
+        assert_eq!(battle.phase, BattlePhase::WaitingForReplacement);
+        assert_eq!(battle.sides[0].needs_replacement, vec![0]);
+        assert!(battle.sides[1].needs_replacement.is_empty());
+        assert!(battle.submit_action(&dex, 1, 1, attack(None)).is_err());
+        battle
+            .submit_action(&dex, 0, 0, Action::Switch { to: 2 })
+            .unwrap()
+            .unwrap();
+        assert_eq!(battle.sides[0].active, vec![2, 1]);
+        assert_eq!(battle.phase, BattlePhase::WaitingForActions);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    battle::{
+        creature::BattleCreature,
+        stats::{Nature, Stats},
+    },
+    dex::Dex,
+};
+use rocket::serde::{Deserialize, Serialize};
+
+pub const MAX_TEAM_SIZE: usize = 6;
+pub const MAX_MOVES: usize = 4;
+
+//The definition of a creature as it is brought into a battle.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct CreatureSet {
+    pub(crate) species: String,
+    #[serde(default)]
+    pub(crate) nickname: Option<String>,
+    pub(crate) level: u8,
+    //Defaults to the first ability of the species.
+    #[serde(default)]
+    pub(crate) ability: Option<String>,
+    #[serde(default)]
+    pub(crate) item: Option<String>,
+    pub(crate) moves: Vec<String>,
+    //Points put into each stat on top of what the species and level give.
+    #[serde(default)]
+    pub(crate) investment: Stats,
+    #[serde(default)]
+    pub(crate) nature: Option<Nature>,
+}
+
+//Teams are checked by validate_team when they are submitted, so building one assumes every
+//species, move, ability and item in it exists.
+impl CreatureSet {
+    pub fn build(&self, dex: &Dex) -> BattleCreature {
+        let species = dex
+            .species
+            .get(&self.species)
+            .expect("validate_team makes sure the species exists");
+        let ability = self.ability.as_ref().unwrap_or(&species.abilities[0]);
+        let moves: Vec<_> = self
+            .moves
+            .iter()
+            .map(|move_id| {
+                dex.moves
+                    .get(move_id)
+                    .expect("validate_team makes sure every move exists")
+            })
+            .collect();
+        let mut creature = BattleCreature::from_species(
+            species,
+            self.level,
+            &self.investment,
+            self.nature,
+            ability,
+            &moves,
+        );
+        creature.item = self.item.clone();
+        creature
+    }
+}
+
+pub fn build_team(dex: &Dex, sets: &[CreatureSet]) -> Vec<BattleCreature> {
+    sets.iter().map(|set| set.build(dex)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        stats::{Nature, Stats},
+        team::{build_team, CreatureSet},
+    };
+    use crate::{
+        dex::{moves::BoostableStat, Dex},
+        DATA_DIRECTORY,
+    };
+
+    fn set(species: &str, moves: &[&str]) -> CreatureSet {
+        CreatureSet {
+            species: String::from(species),
+            nickname: None,
+            level: 50,
+            ability: None,
+            item: None,
+            moves: moves.iter().map(|id| String::from(*id)).collect(),
+            investment: Stats::default(),
+            nature: None,
+        }
+    }
+
+    #[test]
+    fn test_building_a_team() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+
+        let team = build_team(&dex, &[set("tidepup", &["tackle", "water-gun"])]);
+
+        assert_eq!(team[0].species, "tidepup");
+        assert_eq!(team[0].moves.len(), 2);
+        assert_eq!(team[0].current_hp, team[0].stats.hp);
+        assert_eq!(team[0].ability, "torrent-heart");
+    }
+
+    #[test]
+    fn test_picked_abilities_and_items_are_kept() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut plumbird = set("plumbird", &["peck"]);
+        plumbird.ability = Some(String::from("intimidate-glare"));
+        plumbird.item = Some(String::from("oran-berry"));
+
+        let team = build_team(&dex, &[plumbird]);
+
+        assert_eq!(team[0].ability, "intimidate-glare");
+        assert_eq!(team[0].item, Some(String::from("oran-berry")));
+    }
+
+    #[test]
+    fn test_investment_and_nature_change_the_stats() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let plain = set("tidepup", &["tackle"]);
+        let mut invested = plain.clone();
+        invested.investment.speed = 252;
+        invested.nature = Some(Nature {
+            raised: BoostableStat::Attack,
+            lowered: BoostableStat::Defense,
+        });
+
+        let plain = &build_team(&dex, &[plain])[0];
+        let invested = &build_team(&dex, &[invested])[0];
+
+        assert!(invested.stats.speed > plain.stats.speed);
+        assert!(invested.stats.attack > plain.stats.attack);
+        assert!(invested.stats.defense < plain.stats.defense);
+        assert_eq!(invested.stats.hp, plain.stats.hp);
+    }
+}
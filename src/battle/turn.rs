@@ -0,0 +1,479 @@
+use crate::battle::{
+    creature::BattleCreature,
+    hooks::Hit,
+    move_effects::roll_hit_count,
+    order::{order_actions, ActionBracket, TurnAction},
+    rng::BattleRng,
+    targeting::spread_modifier,
+    volatile::VolatileKind,
+    Action, Battle, BattleEvent, BattlePhase, Position,
+};
+use crate::dex::{
+    moves::{BoostableStat, Effect, EffectTarget, FixedDamage, Move, MoveCategory},
+    Dex,
+};
+
+impl Battle {
+    pub(super) fn resolve_turn(&mut self, dex: &Dex) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        let mut rng = BattleRng::for_turn(self.seed, self.turn);
+        let actions = self.take_actions();
+
+        if let Some((position, _)) = actions
+            .iter()
+            .find(|(_, action)| *action == Action::Forfeit)
+        {
+            let side = position.side;
+            events.push(BattleEvent::Forfeited { side });
+            self.declare_winner(1 - side, &mut events);
+            return events;
+        }
+
+        let order = order_actions(&self.turn_actions(dex, &actions), &mut rng);
+        events.push(BattleEvent::TurnOrder {
+            order: order.clone(),
+        });
+        for ordered in order {
+            if self.is_over() {
+                break;
+            }
+            let position = ordered.position;
+            match actions.iter().find(|(from, _)| *from == position) {
+                Some((_, Action::Switch { to })) => {
+                    self.switch_in(dex, &mut rng, position, *to, &mut events)
+                }
+                Some((_, Action::Move { slot, target })) => {
+                    self.use_move(dex, &mut rng, position, *slot, *target, &mut events)
+                }
+                _ => {}
+            }
+        }
+
+        if !self.is_over() {
+            self.end_of_turn(dex, &mut rng, &mut events);
+        }
+        self.turn += 1;
+        if !self.is_over() {
+            self.request_replacements(&mut events);
+            self.submit_forced_actions();
+        }
+        events
+    }
+
+    pub(super) fn resolve_replacements(&mut self, dex: &Dex) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        let mut rng = BattleRng::for_replacements(self.seed, self.turn);
+        let actions = self.take_actions();
+        if let Some((position, _)) = actions
+            .iter()
+            .find(|(_, action)| *action == Action::Forfeit)
+        {
+            let side = position.side;
+            events.push(BattleEvent::Forfeited { side });
+            self.declare_winner(1 - side, &mut events);
+            return events;
+        }
+        let mut replaced = Vec::new();
+        for (position, action) in actions {
+            if let Action::Switch { to } = action {
+                self.place_creature(position, to, &mut events);
+                replaced.push(position);
+            }
+        }
+        for side in self.sides.iter_mut() {
+            side.needs_replacement.clear();
+        }
+        //Every replacement is on the field before any of their switch-in effects happen.
+        for position in replaced {
+            if !self.is_over() {
+                self.entry_hazards(dex, position, &mut events);
+            }
+            if !self.is_over() && !self.creature(position).is_fainted() {
+                self.switch_in_hooks(dex, &mut rng, position, &mut events);
+            }
+        }
+        //A replacement can faint right away from entry hazards and has to be replaced as well.
+        if !self.is_over() {
+            self.phase = BattlePhase::WaitingForActions;
+            self.request_replacements(&mut events);
+            self.submit_forced_actions();
+        }
+        events
+    }
+
+    //Clears the submissions of both sides, keeping the position each action came from.
+    fn take_actions(&mut self) -> Vec<(Position, Action)> {
+        let mut actions = Vec::new();
+        for (side, own_side) in self.sides.iter_mut().enumerate() {
+            for (index, pending) in own_side.pending_actions.iter_mut().enumerate() {
+                if let Some(action) = pending.take() {
+                    actions.push((Position { side, index }, action));
+                }
+            }
+        }
+        actions
+    }
+
+    //A fainted creature stays on the field when there is nothing left on the bench to replace it.
+    pub(super) fn request_replacements(&mut self, events: &mut Vec<BattleEvent>) {
+        for (side, own_side) in self.sides.iter_mut().enumerate() {
+            let fainted: Vec<usize> = (0..own_side.active.len())
+                .filter(|index| own_side.creature_at(*index).is_fainted())
+                .take(own_side.bench().count())
+                .collect();
+            for index in &fainted {
+                events.push(BattleEvent::WaitingForReplacement {
+                    position: Position {
+                        side,
+                        index: *index,
+                    },
+                });
+            }
+            own_side.needs_replacement = fainted;
+        }
+        if self
+            .sides
+            .iter()
+            .any(|side| !side.needs_replacement.is_empty())
+        {
+            self.phase = BattlePhase::WaitingForReplacement;
+        }
+    }
+
+    fn switch_in(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        to: usize,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        self.place_creature(position, to, events);
+        self.entry_hazards(dex, position, events);
+        if !self.creature(position).is_fainted() {
+            self.switch_in_hooks(dex, rng, position, events);
+        }
+    }
+
+    fn place_creature(&mut self, position: Position, to: usize, events: &mut Vec<BattleEvent>) {
+        let own_side = &mut self.sides[position.side];
+        let leaving = own_side.active[position.index];
+        if !own_side.team[leaving].is_fainted() {
+            events.push(BattleEvent::SwitchedOut {
+                position,
+                creature: leaving,
+            });
+        }
+        own_side.team[leaving].reset_volatiles();
+        own_side.active[position.index] = to;
+        own_side.reveal(to);
+        events.push(BattleEvent::SwitchedIn {
+            position,
+            creature: to,
+        });
+    }
+
+    fn turn_actions(&self, dex: &Dex, actions: &[(Position, Action)]) -> Vec<TurnAction> {
+        actions
+            .iter()
+            .filter_map(|(position, action)| {
+                let active = self.creature(*position);
+                let bracket = match action {
+                    Action::Switch { .. } => ActionBracket::Switch,
+                    Action::Move { slot, .. } => ActionBracket::Move {
+                        priority: dex
+                            .moves
+                            .get(&active.moves[*slot].id)
+                            .map(|used_move| used_move.priority)
+                            .unwrap_or(0),
+                    },
+                    Action::Forfeit => return None,
+                };
+                Some(TurnAction {
+                    position: *position,
+                    bracket,
+                    speed: active.effective_stat(dex, BoostableStat::Speed),
+                })
+            })
+            .collect()
+    }
+
+    fn use_move(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        slot: usize,
+        target: Option<Position>,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let Some(used_move) = dex.moves.get(&self.creature(position).moves[slot].id) else {
+            return;
+        };
+        //A forced action only ever lasts for a single turn.
+        self.creature_mut(position).forced_action = None;
+        if self.creature(position).is_fainted() || self.recharge(position, events) {
+            return;
+        }
+        let targets = self.move_targets(position, used_move.target, target);
+        if targets.is_empty()
+            || !self.can_act(dex, rng, position, events)
+            || !self.can_act_despite_volatiles(dex, rng, position, events)
+        {
+            self.end_charge(position, events);
+            return;
+        }
+        //The creature might have been taunted after its move was chosen.
+        if used_move.category == MoveCategory::Status
+            && self.has_volatile(position, VolatileKind::Taunt)
+        {
+            events.push(BattleEvent::PreventedByVolatile {
+                position,
+                volatile: VolatileKind::Taunt,
+            });
+            return;
+        }
+
+        //Charged moves only use up PP on the turn they start charging.
+        let charged = self.has_volatile(position, VolatileKind::Charging);
+        let battle_move = &mut self.creature_mut(position).moves[slot];
+        if !charged {
+            battle_move.pp -= 1;
+        }
+        battle_move.revealed = true;
+        let action = Action::Move { slot, target };
+        if self.charge_move(rng, position, used_move, action, events) {
+            return;
+        }
+        events.push(BattleEvent::UsedMove {
+            position,
+            move_id: used_move.id.clone(),
+        });
+        self.move_used_hooks(dex, rng, position, slot, events);
+        let modifier = spread_modifier(targets.len());
+        for target in targets {
+            if self.is_over() {
+                return;
+            }
+            if !self.creature(target).is_fainted() {
+                let hit = Hit {
+                    attacker: position,
+                    defender: target,
+                    used_move,
+                    effectiveness: 1.0,
+                    modifier,
+                    critical_stage: used_move.critical_stage,
+                    dealt: 0,
+                };
+                self.hit_target(dex, rng, hit, events);
+            }
+        }
+        if used_move.recharge && !self.is_over() && !self.creature(position).is_fainted() {
+            self.start_recharge(rng, position, action, events);
+        }
+    }
+
+    //The hit only carries the spread modifier so far, everything else gets added in here.
+    fn hit_target(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        mut hit: Hit,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let (position, target, used_move) = (hit.attacker, hit.defender, hit.used_move);
+        if target != position {
+            if self.has_volatile(target, VolatileKind::SemiInvulnerable) {
+                events.push(BattleEvent::Missed { position: target });
+                return;
+            }
+            if self.has_volatile(target, VolatileKind::Protection) {
+                events.push(BattleEvent::ProtectedFrom { position: target });
+                return;
+            }
+            if let Some(terrain) = self.terrain_blocking_priority(dex, used_move) {
+                events.push(BattleEvent::PreventedByTerrain {
+                    position: target,
+                    terrain,
+                });
+                return;
+            }
+            if used_move.fixed_damage == Some(FixedDamage::OneHitKnockOut)
+                && self.creature(target).level > self.creature(position).level
+            {
+                events.push(BattleEvent::NoEffect { position: target });
+                return;
+            }
+            if !self.accuracy_check(position, target, used_move).roll(rng) {
+                events.push(BattleEvent::Missed { position: target });
+                return;
+            }
+        }
+        if used_move.category == MoveCategory::Status {
+            if !self.apply_secondary_effects(dex, rng, position, target, used_move, events) {
+                events.push(BattleEvent::NoEffect { position: target });
+            }
+            return;
+        }
+
+        hit.effectiveness = type_effectiveness(dex, self.creature(target), used_move);
+        hit.modifier *= self.field_power_modifier(dex, &used_move.move_type)
+            * self.screen_modifier(dex, target.side, used_move.category);
+        self.before_damage_hooks(dex, rng, &mut hit, events);
+        let hits = roll_hit_count(used_move.hits, rng);
+        let mut total = 0;
+        let mut landed = 0;
+        while landed < hits && !self.creature(target).is_fainted() {
+            let damage = self.hit_damage(dex, rng, &hit);
+            if damage.critical {
+                events.push(BattleEvent::CriticalHit { position: target });
+            }
+            let defender = self.creature_mut(target);
+            let dealt = defender.take_damage(damage.amount);
+            events.push(BattleEvent::Damaged {
+                position: target,
+                amount: dealt,
+                remaining_hp: defender.current_hp,
+            });
+            if dealt > 0 {
+                self.cure_status_by_hit(dex, target, &used_move.move_type, events);
+            }
+            hit.dealt = dealt;
+            self.after_damage_hooks(dex, rng, &hit, events);
+            total += dealt;
+            landed += 1;
+            if self.creature(position).is_fainted() {
+                break;
+            }
+        }
+        if hits > 1 {
+            events.push(BattleEvent::HitTimes {
+                position: target,
+                hits: landed,
+            });
+        }
+        if used_move.fixed_damage == Some(FixedDamage::OneHitKnockOut) && total > 0 {
+            events.push(BattleEvent::OneHitKnockOut { position: target });
+        }
+        self.recoil_and_drain(position, used_move, total, events);
+
+        self.check_fainted(target, events);
+        if target != position {
+            self.check_fainted(position, events);
+        }
+        if total > 0 && !self.is_over() {
+            self.apply_secondary_effects(dex, rng, position, target, used_move, events);
+        }
+    }
+
+    //Returns whether any of the effects actually did something.
+    fn apply_secondary_effects(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        target: Position,
+        used_move: &Move,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let side = position.side;
+        let mut applied = false;
+        for secondary in &used_move.secondary_effects {
+            if !rng.percent(secondary.chance) {
+                continue;
+            }
+            applied |= match &secondary.effect {
+                Effect::Status(kind) => self.apply_status(dex, rng, target, *kind, events),
+                Effect::StatStage {
+                    stat,
+                    stages,
+                    target: EffectTarget::User,
+                } => self.change_stage(position, *stat, *stages, events),
+                Effect::StatStage {
+                    stat,
+                    stages,
+                    target: EffectTarget::Target,
+                } => self.change_stage(target, *stat, *stages, events),
+                Effect::Flinch => self.add_volatile(rng, target, VolatileKind::Flinch, events),
+                Effect::Confusion => {
+                    self.add_volatile(rng, target, VolatileKind::Confusion, events)
+                }
+                Effect::Protect => {
+                    self.add_volatile(rng, position, VolatileKind::Protection, events)
+                }
+                Effect::Redirect => {
+                    self.add_volatile(rng, position, VolatileKind::CenterOfAttention, events)
+                }
+                Effect::Taunt => self.add_volatile(rng, target, VolatileKind::Taunt, events),
+                Effect::LeechSeed => self.add_volatile(rng, target, VolatileKind::Seeded, events),
+                Effect::Weather(kind) => self.set_weather(dex, *kind, events),
+                Effect::Terrain(kind) => self.set_terrain(dex, *kind, events),
+                //Moves laying hazards target the user so they can't miss, so the side comes from the effect itself.
+                Effect::SideCondition {
+                    kind,
+                    target: EffectTarget::User,
+                } => self.add_side_condition(dex, side, *kind, events),
+                Effect::SideCondition {
+                    kind,
+                    target: EffectTarget::Target,
+                } => self.add_side_condition(dex, 1 - side, *kind, events),
+                Effect::ClearSideConditions {
+                    kinds,
+                    target: EffectTarget::User,
+                } => self.clear_side_conditions(side, kinds, events),
+                Effect::ClearSideConditions {
+                    kinds,
+                    target: EffectTarget::Target,
+                } => self.clear_side_conditions(1 - side, kinds, events),
+            };
+        }
+        applied
+    }
+
+    pub(super) fn check_fainted(&mut self, position: Position, events: &mut Vec<BattleEvent>) {
+        if self.is_over() || !self.creature(position).is_fainted() {
+            return;
+        }
+        let own_side = &self.sides[position.side];
+        events.push(BattleEvent::Fainted {
+            position,
+            creature: own_side.active[position.index],
+        });
+        if !own_side.has_usable_creatures() {
+            self.declare_winner(1 - position.side, events);
+        }
+    }
+
+    //Effects which happen once both sides have acted, in the order the positions are stored.
+    fn end_of_turn(&mut self, dex: &Dex, rng: &mut BattleRng, events: &mut Vec<BattleEvent>) {
+        for position in self.positions() {
+            if self.is_over() {
+                return;
+            }
+            self.field_effects(dex, position, events);
+            if !self.is_over() {
+                self.status_damage(dex, position, events);
+            }
+            if !self.is_over() {
+                self.volatiles_end_of_turn(position, events);
+            }
+            if !self.is_over() {
+                self.end_of_turn_hooks(dex, rng, position, events);
+            }
+        }
+        if !self.is_over() {
+            self.field_countdown(events);
+            self.side_conditions_countdown(events);
+        }
+    }
+}
+
+fn type_effectiveness(dex: &Dex, defender: &BattleCreature, used_move: &Move) -> f32 {
+    dex.species
+        .get(&defender.species)
+        .map(|species| {
+            dex.types
+                .effectiveness(&used_move.move_type, &species.types)
+        })
+        .unwrap_or(1.0)
+}
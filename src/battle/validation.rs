@@ -0,0 +1,335 @@
+use crate::{
+    battle::team::{CreatureSet, MAX_MOVES, MAX_TEAM_SIZE},
+    dex::{formats::FormatRules, Dex},
+};
+use rocket::serde::{Deserialize, Serialize};
+
+//Sent as the error_code of a failed request, so clients can react to a broken rule without parsing messages.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum TeamErrorCode {
+    InvalidTeamSize = 1,
+    UnknownSpecies = 2,
+    InvalidLevel = 3,
+    InvalidMoveCount = 4,
+    UnknownMove = 5,
+    IllegalMove = 6,
+    DuplicateMove = 7,
+    IllegalAbility = 8,
+    UnknownItem = 9,
+    TooMuchInvestment = 10,
+    InvalidNature = 11,
+    DuplicateSpecies = 12,
+    DuplicateItem = 13,
+    //The team couldn't even be read, the message says where.
+    InvalidPaste = 14,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct TeamError {
+    pub(crate) error_code: u8,
+    pub(crate) error_message: String,
+    //The index of the creature breaking the rule, missing if the rule is about the whole team.
+    pub(crate) creature: Option<usize>,
+}
+
+impl TeamError {
+    fn new(code: TeamErrorCode, creature: Option<usize>, error_message: String) -> Self {
+        TeamError {
+            error_code: code as u8,
+            error_message,
+            creature,
+        }
+    }
+}
+
+//Collects every broken rule instead of stopping at the first one, so a player can fix them all at once.
+pub fn validate_team(
+    dex: &Dex,
+    rules: &FormatRules,
+    sets: &[CreatureSet],
+) -> Result<(), Vec<TeamError>> {
+    let mut errors = Vec::new();
+    if sets.is_empty() || sets.len() > MAX_TEAM_SIZE {
+        errors.push(TeamError::new(
+            TeamErrorCode::InvalidTeamSize,
+            None,
+            format!("A team needs between 1 and {} creatures.", MAX_TEAM_SIZE),
+        ));
+    }
+    for (index, set) in sets.iter().enumerate() {
+        validate_set(dex, rules, index, set, &mut errors);
+        let earlier = &sets[..index];
+        if rules.species_clause && earlier.iter().any(|other| other.species == set.species) {
+            errors.push(TeamError::new(
+                TeamErrorCode::DuplicateSpecies,
+                Some(index),
+                format!("The team already has a {}.", set.species),
+            ));
+        }
+        if let Some(item) = &set.item {
+            if rules.item_clause
+                && earlier
+                    .iter()
+                    .any(|other| other.item.as_ref() == Some(item))
+            {
+                errors.push(TeamError::new(
+                    TeamErrorCode::DuplicateItem,
+                    Some(index),
+                    format!("Another creature of the team already holds {}.", item),
+                ));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_set(
+    dex: &Dex,
+    rules: &FormatRules,
+    index: usize,
+    set: &CreatureSet,
+    errors: &mut Vec<TeamError>,
+) {
+    let mut error = |code: TeamErrorCode, message: String| {
+        errors.push(TeamError::new(code, Some(index), message));
+    };
+    let species = dex.species.get(&set.species);
+    if species.is_none() {
+        error(
+            TeamErrorCode::UnknownSpecies,
+            format!("{} is not a known species.", set.species),
+        );
+    }
+    if set.level == 0 || set.level > rules.level_cap {
+        error(
+            TeamErrorCode::InvalidLevel,
+            format!(
+                "The level of {} has to be between 1 and {}.",
+                set.species, rules.level_cap
+            ),
+        );
+    }
+    if set.moves.is_empty() || set.moves.len() > MAX_MOVES {
+        error(
+            TeamErrorCode::InvalidMoveCount,
+            format!("{} needs between 1 and {} moves.", set.species, MAX_MOVES),
+        );
+    }
+    for (slot, move_id) in set.moves.iter().enumerate() {
+        if dex.moves.get(move_id).is_none() {
+            error(
+                TeamErrorCode::UnknownMove,
+                format!("{} is not a known move.", move_id),
+            );
+        } else if species.is_some_and(|species| !species.learnset.contains(move_id)) {
+            error(
+                TeamErrorCode::IllegalMove,
+                format!("{} can't learn {}.", set.species, move_id),
+            );
+        }
+        if set.moves[..slot].contains(move_id) {
+            error(
+                TeamErrorCode::DuplicateMove,
+                format!("{} knows {} more than once.", set.species, move_id),
+            );
+        }
+    }
+    if let (Some(species), Some(ability)) = (species, &set.ability) {
+        if !species.abilities.contains(ability) {
+            error(
+                TeamErrorCode::IllegalAbility,
+                format!("{} can't have the ability {}.", set.species, ability),
+            );
+        }
+    }
+    if let Some(item) = &set.item {
+        if dex.items.get(item).is_none() {
+            error(
+                TeamErrorCode::UnknownItem,
+                format!("{} is not a known item.", item),
+            );
+        }
+    }
+    let investment = set.investment.values();
+    if investment
+        .iter()
+        .any(|invested| *invested > rules.max_stat_investment)
+    {
+        error(
+            TeamErrorCode::TooMuchInvestment,
+            format!(
+                "{} can't have more than {} points invested into a single stat.",
+                set.species, rules.max_stat_investment
+            ),
+        );
+    }
+    let total: u32 = investment.iter().map(|invested| *invested as u32).sum();
+    if total > rules.max_total_investment as u32 {
+        error(
+            TeamErrorCode::TooMuchInvestment,
+            format!(
+                "{} can't have more than {} points invested in total.",
+                set.species, rules.max_total_investment
+            ),
+        );
+    }
+    let invalid_nature = set.nature.is_some_and(|nature| !nature.is_valid());
+    if invalid_nature {
+        error(
+            TeamErrorCode::InvalidNature,
+            format!(
+                "The nature of {} can't change accuracy or evasion.",
+                set.species
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        stats::{Nature, Stats},
+        team::CreatureSet,
+        validation::{validate_team, TeamError, TeamErrorCode},
+        BattleFormat,
+    };
+    use crate::{
+        dex::{moves::BoostableStat, Dex},
+        DATA_DIRECTORY,
+    };
+
+    fn set(species: &str, moves: &[&str]) -> CreatureSet {
+        CreatureSet {
+            species: String::from(species),
+            nickname: None,
+            level: 50,
+            ability: None,
+            item: None,
+            moves: moves.iter().map(|id| String::from(*id)).collect(),
+            investment: Stats::default(),
+            nature: None,
+        }
+    }
+
+    fn codes(errors: Vec<TeamError>) -> Vec<(u8, Option<usize>)> {
+        errors
+            .into_iter()
+            .map(|error| (error.error_code, error.creature))
+            .collect()
+    }
+
+    #[test]
+    fn test_legal_teams_pass() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut tidepup = set("tidepup", &["tackle", "water-gun"]);
+        tidepup.ability = Some(String::from("torrent-heart"));
+        tidepup.item = Some(String::from("oran-berry"));
+        tidepup.investment.speed = 252;
+        tidepup.investment.attack = 252;
+
+        let team = [tidepup, set("sproutle", &["vine-whip"])];
+
+        assert!(validate_team(&dex, dex.formats.get(BattleFormat::Singles), &team).is_ok());
+    }
+
+    #[test]
+    fn test_every_broken_rule_is_reported() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut frostfang = set("frostfang", &["ice-beam", "water-gun", "ice-beam"]);
+        frostfang.ability = Some(String::from("torrent-heart"));
+        frostfang.level = 101;
+        let team = [set("missingno", &["splash-of-doom"]), frostfang];
+
+        let errors = validate_team(&dex, dex.formats.get(BattleFormat::Singles), &team);
+
+        assert_eq!(
+            codes(errors.unwrap_err()),
+            vec![
+                (TeamErrorCode::UnknownSpecies as u8, Some(0)),
+                (TeamErrorCode::UnknownMove as u8, Some(0)),
+                (TeamErrorCode::InvalidLevel as u8, Some(1)),
+                (TeamErrorCode::IllegalMove as u8, Some(1)),
+                (TeamErrorCode::DuplicateMove as u8, Some(1)),
+                (TeamErrorCode::IllegalAbility as u8, Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_investment_limits() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let rules = dex.formats.get(BattleFormat::Singles);
+        let mut tidepup = set("tidepup", &["tackle"]);
+        tidepup.investment.hp = 253;
+        let mut spread = set("tidepup", &["tackle"]);
+        spread.investment = Stats {
+            hp: 252,
+            attack: 252,
+            defense: 8,
+            special_attack: 0,
+            special_defense: 0,
+            speed: 0,
+        };
+
+        assert_eq!(
+            codes(validate_team(&dex, rules, &[tidepup]).unwrap_err()),
+            vec![(TeamErrorCode::TooMuchInvestment as u8, Some(0))]
+        );
+        assert_eq!(
+            codes(validate_team(&dex, rules, &[spread]).unwrap_err()),
+            vec![(TeamErrorCode::TooMuchInvestment as u8, Some(0))]
+        );
+    }
+
+    #[test]
+    fn test_clauses_depend_on_the_format() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut first = set("tidepup", &["tackle"]);
+        first.item = Some(String::from("oran-berry"));
+        let mut second = set("sproutle", &["tackle"]);
+        second.item = Some(String::from("oran-berry"));
+        let team = [first.clone(), second, first];
+
+        assert_eq!(
+            codes(validate_team(&dex, dex.formats.get(BattleFormat::Singles), &team).unwrap_err()),
+            vec![(TeamErrorCode::DuplicateSpecies as u8, Some(2))]
+        );
+        assert_eq!(
+            codes(validate_team(&dex, dex.formats.get(BattleFormat::Doubles), &team).unwrap_err()),
+            vec![
+                (TeamErrorCode::DuplicateItem as u8, Some(1)),
+                (TeamErrorCode::DuplicateSpecies as u8, Some(2)),
+                (TeamErrorCode::DuplicateItem as u8, Some(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_team_size_moves_items_and_natures_are_checked() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let rules = dex.formats.get(BattleFormat::Singles);
+        let mut tidepup = set("tidepup", &[]);
+        tidepup.item = Some(String::from("master-ball"));
+        tidepup.nature = Some(Nature {
+            raised: BoostableStat::Evasion,
+            lowered: BoostableStat::Defense,
+        });
+
+        assert_eq!(
+            codes(validate_team(&dex, rules, &[]).unwrap_err()),
+            vec![(TeamErrorCode::InvalidTeamSize as u8, None)]
+        );
+        assert_eq!(
+            codes(validate_team(&dex, rules, &[tidepup]).unwrap_err()),
+            vec![
+                (TeamErrorCode::InvalidMoveCount as u8, Some(0)),
+                (TeamErrorCode::UnknownItem as u8, Some(0)),
+                (TeamErrorCode::InvalidNature as u8, Some(0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,454 @@
+use crate::battle::{
+    creature::{BattleCreature, BattleMove},
+    field::Field,
+    side_conditions::ActiveSideCondition,
+    stats::Stats,
+    volatile::{StatStages, VolatileKind},
+    Battle, BattleEvent, BattleFormat, BattlePhase, Side,
+};
+use crate::dex::moves::StatusKind;
+use rocket::serde::{Deserialize, Serialize};
+
+//What a single player is allowed to know about the battle.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct BattleView {
+    pub(crate) turn: u32,
+    pub(crate) phase: BattlePhase,
+    pub(crate) format: BattleFormat,
+    pub(crate) side: usize,
+    pub(crate) winner: Option<usize>,
+    //Weather and terrain are visible to both players, including the turns they still last.
+    pub(crate) field: Field,
+    pub(crate) own: OwnSideView,
+    pub(crate) opponent: OpponentSideView,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct OwnSideView {
+    //The team index of the creature in each position.
+    pub(crate) active: Vec<usize>,
+    //One entry per position.
+    pub(crate) action_submitted: Vec<bool>,
+    //Positions whose creature is charging or recharging get their action submitted automatically.
+    pub(crate) locked_in: Vec<bool>,
+    pub(crate) needs_replacement: Vec<usize>,
+    pub(crate) conditions: Vec<ActiveSideCondition>,
+    pub(crate) team: Vec<OwnCreatureView>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct OwnCreatureView {
+    pub(crate) species: String,
+    pub(crate) level: u8,
+    pub(crate) stats: Stats,
+    pub(crate) current_hp: u16,
+    pub(crate) hp_percent: u8,
+    pub(crate) status: Option<StatusKind>,
+    pub(crate) stages: StatStages,
+    pub(crate) volatiles: Vec<VolatileKind>,
+    pub(crate) ability: String,
+    pub(crate) item: Option<String>,
+    //Set while a choice item only allows one of the moves to be selected.
+    pub(crate) locked_move: Option<usize>,
+    pub(crate) moves: Vec<BattleMove>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct OpponentSideView {
+    pub(crate) action_submitted: bool,
+    pub(crate) conditions: Vec<ActiveSideCondition>,
+    pub(crate) team: Vec<OpponentCreatureView>,
+    pub(crate) unrevealed: usize,
+}
+
+//Only contains creatures which have been on the field, in the order they got there, and moves which have been used.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct OpponentCreatureView {
+    pub(crate) species: String,
+    pub(crate) level: u8,
+    pub(crate) hp_percent: u8,
+    pub(crate) status: Option<StatusKind>,
+    pub(crate) stages: StatStages,
+    pub(crate) volatiles: Vec<VolatileKind>,
+    //Only known once it did something.
+    pub(crate) ability: Option<String>,
+    pub(crate) item: Option<String>,
+    pub(crate) active: bool,
+    pub(crate) moves: Vec<String>,
+}
+
+impl From<&BattleCreature> for OwnCreatureView {
+    fn from(creature: &BattleCreature) -> Self {
+        OwnCreatureView {
+            species: creature.species.clone(),
+            level: creature.level,
+            stats: creature.stats,
+            current_hp: creature.current_hp,
+            hp_percent: creature.hp_percent(),
+            status: creature.status.map(|status| status.kind),
+            stages: creature.stages,
+            volatiles: volatile_kinds(creature),
+            ability: creature.ability.clone(),
+            item: creature.item.clone(),
+            locked_move: creature.locked_move,
+            moves: creature.moves.clone(),
+        }
+    }
+}
+
+impl OwnSideView {
+    fn new(side: &Side) -> Self {
+        OwnSideView {
+            active: side.active.clone(),
+            action_submitted: side.pending_actions.iter().map(Option::is_some).collect(),
+            locked_in: (0..side.active.len())
+                .map(|index| side.creature_at(index).forced_action.is_some())
+                .collect(),
+            needs_replacement: side.needs_replacement.clone(),
+            conditions: side.conditions.clone(),
+            team: side.team.iter().map(OwnCreatureView::from).collect(),
+        }
+    }
+}
+
+impl OpponentSideView {
+    fn new(side: &Side) -> Self {
+        let team: Vec<OpponentCreatureView> = side
+            .reveal_order
+            .iter()
+            .map(|&index| (index, &side.team[index]))
+            .map(|(index, creature)| OpponentCreatureView {
+                species: creature.species.clone(),
+                level: creature.level,
+                hp_percent: creature.hp_percent(),
+                status: creature.status.map(|status| status.kind),
+                stages: creature.stages,
+                volatiles: volatile_kinds(creature),
+                ability: creature.ability_revealed.then(|| creature.ability.clone()),
+                item: creature.item.clone().filter(|_| creature.item_revealed),
+                active: side.active.contains(&index),
+                moves: creature
+                    .moves
+                    .iter()
+                    .filter(|known| known.revealed)
+                    .map(|known| known.id.clone())
+                    .collect(),
+            })
+            .collect();
+        OpponentSideView {
+            action_submitted: side.pending_actions.iter().any(Option::is_some),
+            conditions: side.conditions.clone(),
+            unrevealed: side.team.len() - team.len(),
+            team,
+        }
+    }
+}
+
+//How many turns are left is not something the opponent would know, so it is left out for both sides.
+fn volatile_kinds(creature: &BattleCreature) -> Vec<VolatileKind> {
+    creature
+        .volatiles
+        .iter()
+        .map(|volatile| volatile.kind)
+        .collect()
+}
+
+impl Battle {
+    pub fn view_for(&self, side: usize) -> BattleView {
+        BattleView {
+            turn: self.turn,
+            phase: self.phase,
+            format: self.format,
+            side,
+            winner: self.winner,
+            field: self.field,
+            own: OwnSideView::new(&self.sides[side]),
+            opponent: OpponentSideView::new(&self.sides[1 - side]),
+        }
+    }
+
+    //Events about the opponent only contain what the battle view shows: HP in percent of the max HP,
+    //and creatures as their index in the opponent team of the view instead of their team slot.
+    //Has to be called right after the events happened, because it looks at the current state.
+    pub fn events_for(&self, side: usize, events: &[BattleEvent]) -> Vec<BattleEvent> {
+        let opponent = &self.sides[1 - side];
+        let view_index = |creature: usize| {
+            opponent
+                .reveal_order
+                .iter()
+                .position(|&revealed| revealed == creature)
+                .unwrap_or(creature)
+        };
+        //Going backwards from the current state tells which creature was on the field for each event.
+        let mut active = opponent.active.clone();
+        let mut projected: Vec<BattleEvent> = events
+            .iter()
+            .rev()
+            .map(|event| {
+                let mut event = event.clone();
+                match &mut event {
+                    BattleEvent::Damaged {
+                        position,
+                        amount,
+                        remaining_hp,
+                    }
+                    | BattleEvent::HurtByRecoil {
+                        position,
+                        amount,
+                        remaining_hp,
+                    }
+                    | BattleEvent::HurtByWeather {
+                        position,
+                        amount,
+                        remaining_hp,
+                        ..
+                    }
+                    | BattleEvent::HurtByHazard {
+                        position,
+                        amount,
+                        remaining_hp,
+                        ..
+                    }
+                    | BattleEvent::Drained {
+                        position,
+                        amount,
+                        remaining_hp,
+                    }
+                    | BattleEvent::HurtByStatus {
+                        position,
+                        amount,
+                        remaining_hp,
+                        ..
+                    } if position.side != side => {
+                        let creature = &opponent.team[active[position.index]];
+                        let before = creature.percent_of(remaining_hp.saturating_add(*amount));
+                        let after = creature.percent_of(*remaining_hp);
+                        *amount = (before - after) as u16;
+                        *remaining_hp = after as u16;
+                    }
+                    BattleEvent::Healed {
+                        position,
+                        amount,
+                        current_hp,
+                    } if position.side != side => {
+                        let creature = &opponent.team[active[position.index]];
+                        let before = creature.percent_of(current_hp.saturating_sub(*amount));
+                        let after = creature.percent_of(*current_hp);
+                        *amount = (after - before) as u16;
+                        *current_hp = after as u16;
+                    }
+                    BattleEvent::Fainted { position, creature }
+                    | BattleEvent::SwitchedOut { position, creature }
+                    | BattleEvent::SwitchedIn { position, creature }
+                        if position.side != side =>
+                    {
+                        active[position.index] = *creature;
+                        *creature = view_index(*creature);
+                    }
+                    _ => {}
+                }
+                event
+            })
+            .collect();
+        projected.reverse();
+        projected
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        stats::Stats, team::build_team, team::CreatureSet, Action, Battle, BattleEvent,
+        BattleFormat,
+    };
+    use crate::{dex::Dex, DATA_DIRECTORY};
+
+    fn battle(dex: &Dex) -> Battle {
+        let set = |species: &str, moves: &[&str]| CreatureSet {
+            species: String::from(species),
+            nickname: None,
+            level: 50,
+            ability: None,
+            item: None,
+            moves: moves.iter().map(|id| String::from(*id)).collect(),
+            investment: Stats::default(),
+            nature: None,
+        };
+        let first = build_team(
+            dex,
+            &[
+                set("tidepup", &["tackle", "water-gun"]),
+                set("sproutle", &["vine-whip"]),
+            ],
+        );
+        let second = build_team(
+            dex,
+            &[
+                set("cindercub", &["tackle", "ember"]),
+                set("voltmouse", &["spark"]),
+                set("pebblon", &["rock-throw"]),
+            ],
+        );
+        Battle::new(BattleFormat::Singles, [first, second], 1)
+    }
+
+    #[test]
+    fn test_own_side_is_fully_visible() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+
+        let view = battle(&dex).view_for(0);
+
+        assert_eq!(view.own.team.len(), 2);
+        assert_eq!(view.own.team[0].moves.len(), 2);
+        assert_eq!(view.own.team[0].hp_percent, 100);
+    }
+
+    #[test]
+    fn test_opponent_only_shows_revealed_information() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+
+        let view = battle(&dex).view_for(0);
+
+        assert_eq!(view.opponent.team.len(), 1);
+        assert_eq!(view.opponent.unrevealed, 2);
+        assert_eq!(view.opponent.team[0].species, "cindercub");
+        assert!(view.opponent.team[0].active);
+        assert!(view.opponent.team[0].moves.is_empty());
+    }
+
+    #[test]
+    fn test_used_moves_get_revealed() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(&dex);
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        assert!(battle.view_for(1).opponent.action_submitted);
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 1,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        let view = battle.view_for(0);
+        assert_eq!(view.opponent.team[0].moves, vec![String::from("ember")]);
+        assert!(view.opponent.team[0].hp_percent < 100);
+        assert_eq!(view.turn, 2);
+    }
+
+    #[test]
+    fn test_opponent_events_only_show_hp_in_percent() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(&dex);
+        let tackle = Action::Move {
+            slot: 0,
+            target: None,
+        };
+
+        battle.submit_action(&dex, 0, 0, tackle).unwrap();
+        let events = battle.submit_action(&dex, 1, 0, tackle).unwrap().unwrap();
+
+        let own = battle.sides[0].creature_at(0);
+        let opponent_hp = battle.sides[1].creature_at(0).current_hp;
+        let opponent_percent = battle.view_for(0).opponent.team[0].hp_percent as u16;
+        let projected = battle.events_for(0, &events);
+        let damaged: Vec<(usize, u16, u16)> = projected
+            .iter()
+            .filter_map(|event| match event {
+                BattleEvent::Damaged {
+                    position,
+                    amount,
+                    remaining_hp,
+                } => Some((position.side, *amount, *remaining_hp)),
+                _ => None,
+            })
+            .collect();
+        assert_ne!(opponent_hp, opponent_percent);
+        assert_eq!(damaged.len(), 2);
+        assert!(damaged.contains(&(1, 100 - opponent_percent, opponent_percent)));
+        assert!(damaged.contains(&(0, own.stats.hp - own.current_hp, own.current_hp)));
+    }
+
+    #[test]
+    fn test_opponent_events_hide_team_slots() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(&dex);
+
+        battle
+            .submit_action(&dex, 0, 0, Action::Switch { to: 1 })
+            .unwrap();
+        let events = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 2 })
+            .unwrap()
+            .unwrap();
+
+        let switched_in = |events: &[BattleEvent], side: usize| {
+            events.iter().find_map(|event| match event {
+                BattleEvent::SwitchedIn { position, creature } if position.side == side => {
+                    Some(*creature)
+                }
+                _ => None,
+            })
+        };
+        let projected = battle.events_for(0, &events);
+        assert_eq!(switched_in(&events, 1), Some(2));
+        assert_eq!(switched_in(&projected, 1), Some(1));
+        assert_eq!(switched_in(&projected, 0), Some(1));
+        assert_eq!(battle.view_for(0).opponent.team[1].species, "pebblon");
+    }
+
+    #[test]
+    fn test_opponent_indices_do_not_change_when_lower_slots_get_revealed() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = battle(&dex);
+        let tackle = Action::Move {
+            slot: 0,
+            target: None,
+        };
+        let switched_in = |events: &[BattleEvent]| {
+            events.iter().find_map(|event| match event {
+                BattleEvent::SwitchedIn { position, creature } if position.side == 1 => {
+                    Some(*creature)
+                }
+                _ => None,
+            })
+        };
+
+        battle.submit_action(&dex, 0, 0, tackle).unwrap();
+        let first = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 2 })
+            .unwrap()
+            .unwrap();
+        assert_eq!(switched_in(&battle.events_for(0, &first)), Some(1));
+        battle.submit_action(&dex, 0, 0, tackle).unwrap();
+        let second = battle
+            .submit_action(&dex, 1, 0, Action::Switch { to: 1 })
+            .unwrap()
+            .unwrap();
+
+        let projected = battle.events_for(0, &second);
+        assert_eq!(switched_in(&projected), Some(2));
+        assert!(projected.iter().any(|event| matches!(
+            event,
+            BattleEvent::SwitchedOut { position, creature: 1 } if position.side == 1
+        )));
+        let view = battle.view_for(0);
+        assert_eq!(view.opponent.team[1].species, "pebblon");
+        assert_eq!(view.opponent.team[2].species, "voltmouse");
+    }
+}
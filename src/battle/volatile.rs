@@ -0,0 +1,544 @@
+use crate::battle::{
+    damage::{calculate_damage_with_rolls, DamageRequest, DamageRolls, MAX_ROLL, MIN_ROLL},
+    rng::BattleRng,
+    Battle, BattleEvent, Position,
+};
+use crate::dex::{moves::BoostableStat, statuses::Fraction, Dex};
+use rocket::serde::{Deserialize, Serialize};
+
+pub const MIN_STAGE: i8 = -6;
+pub const MAX_STAGE: i8 = 6;
+pub const CONFUSION_MIN_TURNS: u8 = 2;
+pub const CONFUSION_MAX_TURNS: u8 = 5;
+pub const CONFUSION_SELF_HIT_CHANCE: u8 = 33;
+//Hitting itself in confusion works like a typeless physical move with this power.
+pub const CONFUSION_POWER: u16 = 40;
+pub const TAUNT_TURNS: u8 = 3;
+pub const SEED_DRAIN: Fraction = Fraction {
+    numerator: 1,
+    denominator: 8,
+};
+
+//Everything in here only lasts while the creature stays on the field.
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct StatStages {
+    pub(crate) attack: i8,
+    pub(crate) defense: i8,
+    pub(crate) special_attack: i8,
+    pub(crate) special_defense: i8,
+    pub(crate) speed: i8,
+    pub(crate) accuracy: i8,
+    pub(crate) evasion: i8,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum VolatileKind {
+    Confusion,
+    //Only lasts for the rest of the turn it was inflicted in.
+    Flinch,
+    //Blocks moves aimed at the creature for the rest of the turn.
+    Protection,
+    //Locks the creature out of status moves.
+    Taunt,
+    //Loses health at the end of every turn, which heals the opposing creature.
+    Seeded,
+    //Foes have to aim their single target moves at the creature for the rest of the turn.
+    CenterOfAttention,
+    //Uses the charged move on its next turn.
+    Charging,
+    //Can't be hit while charging certain moves.
+    SemiInvulnerable,
+    //Has to skip its next turn.
+    Recharging,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Volatile {
+    pub(crate) kind: VolatileKind,
+    pub(crate) turns_left: Option<u8>,
+}
+
+impl StatStages {
+    pub fn get(&self, stat: BoostableStat) -> i8 {
+        match stat {
+            BoostableStat::Attack => self.attack,
+            BoostableStat::Defense => self.defense,
+            BoostableStat::SpecialAttack => self.special_attack,
+            BoostableStat::SpecialDefense => self.special_defense,
+            BoostableStat::Speed => self.speed,
+            BoostableStat::Accuracy => self.accuracy,
+            BoostableStat::Evasion => self.evasion,
+        }
+    }
+
+    //Returns by how much the stage actually changed, stages never leave -6..+6.
+    pub fn change(&mut self, stat: BoostableStat, by: i8) -> i8 {
+        let stage = match stat {
+            BoostableStat::Attack => &mut self.attack,
+            BoostableStat::Defense => &mut self.defense,
+            BoostableStat::SpecialAttack => &mut self.special_attack,
+            BoostableStat::SpecialDefense => &mut self.special_defense,
+            BoostableStat::Speed => &mut self.speed,
+            BoostableStat::Accuracy => &mut self.accuracy,
+            BoostableStat::Evasion => &mut self.evasion,
+        };
+        let before = *stage;
+        *stage = before.saturating_add(by).clamp(MIN_STAGE, MAX_STAGE);
+        *stage - before
+    }
+
+    //+1 means 1.5x for the regular stats, accuracy and evasion scale slower.
+    pub fn multiplier(stat: BoostableStat, stage: i8) -> f32 {
+        let base = match stat {
+            BoostableStat::Accuracy | BoostableStat::Evasion => 3.0,
+            _ => 2.0,
+        };
+        let stage = stage.clamp(MIN_STAGE, MAX_STAGE) as f32;
+        if stage >= 0.0 {
+            (base + stage) / base
+        } else {
+            base / (base - stage)
+        }
+    }
+}
+
+impl Battle {
+    pub(super) fn has_volatile(&self, position: Position, kind: VolatileKind) -> bool {
+        self.creature(position)
+            .volatiles
+            .iter()
+            .any(|volatile| volatile.kind == kind)
+    }
+
+    //Returns whether the volatile was added, every kind can only be active once.
+    pub(super) fn add_volatile(
+        &mut self,
+        rng: &mut BattleRng,
+        position: Position,
+        kind: VolatileKind,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        if self.creature(position).is_fainted() || self.has_volatile(position, kind) {
+            return false;
+        }
+        let turns_left = match kind {
+            VolatileKind::Confusion => {
+                Some(rng.range(CONFUSION_MIN_TURNS as u32, CONFUSION_MAX_TURNS as u32) as u8)
+            }
+            VolatileKind::Taunt => Some(TAUNT_TURNS),
+            _ => None,
+        };
+        self.creature_mut(position)
+            .volatiles
+            .push(Volatile { kind, turns_left });
+        events.push(BattleEvent::VolatileStarted {
+            position,
+            volatile: kind,
+        });
+        true
+    }
+
+    pub(super) fn remove_volatile(
+        &mut self,
+        position: Position,
+        kind: VolatileKind,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        let volatiles = &mut self.creature_mut(position).volatiles;
+        let before = volatiles.len();
+        volatiles.retain(|volatile| volatile.kind != kind);
+        if volatiles.len() != before {
+            events.push(BattleEvent::VolatileEnded {
+                position,
+                volatile: kind,
+            });
+        }
+    }
+
+    pub(super) fn change_stage(
+        &mut self,
+        position: Position,
+        stat: BoostableStat,
+        by: i8,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        let creature = self.creature_mut(position);
+        if creature.is_fainted() {
+            return false;
+        }
+        let change = creature.stages.change(stat, by);
+        if change == 0 {
+            return false;
+        }
+        events.push(BattleEvent::StatStageChanged {
+            position,
+            stat,
+            change,
+            stage: creature.stages.get(stat),
+        });
+        true
+    }
+
+    //Flinching and confusion are checked right after the major status, before a move is used.
+    pub(super) fn can_act_despite_volatiles(
+        &mut self,
+        dex: &Dex,
+        rng: &mut BattleRng,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) -> bool {
+        if self.has_volatile(position, VolatileKind::Flinch) {
+            events.push(BattleEvent::PreventedByVolatile {
+                position,
+                volatile: VolatileKind::Flinch,
+            });
+            return false;
+        }
+        if !self.has_volatile(position, VolatileKind::Confusion) {
+            return true;
+        }
+        let creature = self.creature_mut(position);
+        let Some(confusion) = creature
+            .volatiles
+            .iter_mut()
+            .find(|volatile| volatile.kind == VolatileKind::Confusion)
+        else {
+            return true;
+        };
+        let turns_left = confusion.turns_left.unwrap_or(0);
+        if turns_left == 0 {
+            self.remove_volatile(position, VolatileKind::Confusion, events);
+            return true;
+        }
+        confusion.turns_left = Some(turns_left - 1);
+        if !rng.percent(CONFUSION_SELF_HIT_CHANCE) {
+            return true;
+        }
+
+        let request = DamageRequest {
+            level: creature.level,
+            power: CONFUSION_POWER,
+            attack: creature.effective_stat(dex, BoostableStat::Attack),
+            defense: creature.effective_stat(dex, BoostableStat::Defense),
+            stab: false,
+            effectiveness: 1.0,
+            modifier: 1.0,
+            critical_multiplier: dex.mechanics.critical_hit.multiplier,
+        };
+        let rolls = DamageRolls {
+            critical: false,
+            roll: rng.range(MIN_ROLL as u32, MAX_ROLL as u32) as u8,
+        };
+        let dealt = creature.take_damage(calculate_damage_with_rolls(&request, rolls).amount);
+        events.push(BattleEvent::PreventedByVolatile {
+            position,
+            volatile: VolatileKind::Confusion,
+        });
+        events.push(BattleEvent::Damaged {
+            position,
+            amount: dealt,
+            remaining_hp: creature.current_hp,
+        });
+        self.check_fainted(position, events);
+        false
+    }
+
+    //Drains seeded creatures, then lets everything which only lasts a turn (or a few) run out.
+    pub(super) fn volatiles_end_of_turn(
+        &mut self,
+        position: Position,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        if self.has_volatile(position, VolatileKind::Seeded) {
+            let seeded = self.creature_mut(position);
+            if !seeded.is_fainted() {
+                let drained = seeded.take_damage(SEED_DRAIN.of(seeded.stats.hp));
+                events.push(BattleEvent::Drained {
+                    position,
+                    amount: drained,
+                    remaining_hp: seeded.current_hp,
+                });
+                if let Some(opponent) = self.seed_receiver(position) {
+                    let receiver = self.creature_mut(opponent);
+                    let healed = receiver.heal(drained);
+                    events.push(BattleEvent::Healed {
+                        position: opponent,
+                        amount: healed,
+                        current_hp: receiver.current_hp,
+                    });
+                }
+                self.check_fainted(position, events);
+            }
+        }
+
+        self.remove_volatile(position, VolatileKind::Flinch, events);
+        self.remove_volatile(position, VolatileKind::Protection, events);
+        self.remove_volatile(position, VolatileKind::CenterOfAttention, events);
+        let taunt = self
+            .creature_mut(position)
+            .volatiles
+            .iter_mut()
+            .find(|volatile| volatile.kind == VolatileKind::Taunt);
+        if let Some(taunt) = taunt {
+            let turns_left = taunt.turns_left.unwrap_or(0).saturating_sub(1);
+            taunt.turns_left = Some(turns_left);
+            if turns_left == 0 {
+                self.remove_volatile(position, VolatileKind::Taunt, events);
+            }
+        }
+    }
+
+    //The foe across from the seeded creature gets the health, or any other foe if that one fainted.
+    fn seed_receiver(&self, position: Position) -> Option<Position> {
+        let foes = self.foes(position);
+        let across = foes.iter().find(|foe| foe.index == position.index);
+        across
+            .into_iter()
+            .chain(foes.iter())
+            .find(|foe| !self.creature(**foe).is_fainted())
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{
+        creature::{BattleCreature, BattleMove},
+        rng::BattleRng,
+        stats::Stats,
+        volatile::{StatStages, VolatileKind},
+        Action, Battle, BattleError, BattleEvent, BattleFormat, Position,
+    };
+    use crate::dex::{moves::BoostableStat, Dex};
+    use crate::DATA_DIRECTORY;
+
+    fn creature(moves: &[&str]) -> BattleCreature {
+        BattleCreature::new(
+            "tidepup",
+            50,
+            Stats {
+                hp: 160,
+                attack: 100,
+                defense: 100,
+                special_attack: 100,
+                special_defense: 100,
+                speed: 100,
+            },
+            moves
+                .iter()
+                .map(|id| BattleMove {
+                    id: String::from(*id),
+                    pp: 35,
+                    revealed: false,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_stages_are_clamped() {
+        let mut stages = StatStages::default();
+
+        assert_eq!(stages.change(BoostableStat::Attack, 4), 4);
+        assert_eq!(stages.change(BoostableStat::Attack, 4), 2);
+        assert_eq!(stages.change(BoostableStat::Attack, 1), 0);
+        assert_eq!(stages.get(BoostableStat::Attack), 6);
+        assert_eq!(stages.change(BoostableStat::Evasion, -12), -6);
+    }
+
+    #[test]
+    fn test_stage_multipliers() {
+        assert_eq!(StatStages::multiplier(BoostableStat::Attack, 0), 1.0);
+        assert_eq!(StatStages::multiplier(BoostableStat::Attack, 2), 2.0);
+        assert_eq!(StatStages::multiplier(BoostableStat::Speed, -2), 0.5);
+        assert_eq!(StatStages::multiplier(BoostableStat::Attack, 6), 4.0);
+        assert_eq!(StatStages::multiplier(BoostableStat::Accuracy, 3), 2.0);
+        assert_eq!(StatStages::multiplier(BoostableStat::Evasion, -3), 0.5);
+    }
+
+    #[test]
+    fn test_stages_change_effective_stats() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut boosted = creature(&["tackle"]);
+
+        boosted.stages.change(BoostableStat::Attack, 2);
+
+        assert_eq!(boosted.effective_stat(&dex, BoostableStat::Attack), 200);
+    }
+
+    #[test]
+    fn test_switching_out_resets_stages_and_volatiles() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(&["sharpen-claws"]), creature(&["tackle"])],
+                vec![creature(&["provoke"])],
+            ],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(battle.sides[0].team[0].stages.attack, 2);
+        assert!(battle.has_volatile(Position { side: 0, index: 0 }, VolatileKind::Taunt));
+
+        battle
+            .submit_action(&dex, 0, 0, Action::Switch { to: 1 })
+            .unwrap();
+        battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(battle.sides[0].team[0].stages, StatStages::default());
+        assert!(battle.sides[0].team[0].volatiles.is_empty());
+    }
+
+    #[test]
+    fn test_taunted_creatures_cant_pick_status_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [
+                vec![creature(&["sharpen-claws", "tackle"])],
+                vec![creature(&["provoke"])],
+            ],
+            1,
+        );
+        battle.add_volatile(
+            &mut BattleRng::new(1),
+            Position { side: 0, index: 0 },
+            VolatileKind::Taunt,
+            &mut Vec::new(),
+        );
+
+        assert!(matches!(
+            battle.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                }
+            ),
+            Err(BattleError::InvalidAction(_))
+        ));
+        assert!(battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 1,
+                    target: None,
+                }
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_protection_blocks_moves() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [vec![creature(&["guard-up"])], vec![creature(&["tackle"])]],
+            1,
+        );
+
+        battle
+            .submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap();
+        let events = battle
+            .submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        assert!(events.contains(&BattleEvent::ProtectedFrom {
+            position: Position { side: 0, index: 0 }
+        }));
+        assert_eq!(battle.sides[0].creature_at(0).current_hp, 160);
+        assert!(!battle.has_volatile(Position { side: 0, index: 0 }, VolatileKind::Protection));
+    }
+
+    #[test]
+    fn test_seeded_creatures_heal_the_opponent() {
+        let mut hurt = creature(&["tackle"]);
+        hurt.current_hp = 100;
+        let mut battle = Battle::new(
+            BattleFormat::Singles,
+            [vec![hurt], vec![creature(&["sharpen-claws"])]],
+            1,
+        );
+        battle.add_volatile(
+            &mut BattleRng::new(1),
+            Position { side: 1, index: 0 },
+            VolatileKind::Seeded,
+            &mut Vec::new(),
+        );
+        let mut events = Vec::new();
+
+        battle.volatiles_end_of_turn(Position { side: 1, index: 0 }, &mut events);
+
+        assert_eq!(
+            events,
+            vec![
+                BattleEvent::Drained {
+                    position: Position { side: 1, index: 0 },
+                    amount: 20,
+                    remaining_hp: 140
+                },
+                BattleEvent::Healed {
+                    position: Position { side: 0, index: 0 },
+                    amount: 20,
+                    current_hp: 120
+                }
+            ]
+        );
+    }
+}
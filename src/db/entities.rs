@@ -1,17 +1,274 @@
+use crate::{
+    battle::{
+        team::{build_team, CreatureSet},
+        validation::validate_team,
+        Action, Battle, BattleError, BattleEvent, BattleFormat,
+    },
+    dex::Dex,
+};
 use rocket::serde::{Deserialize, Serialize};
 use surrealdb::sql::{Id, Thing};
+use uuid::Uuid;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Game {
     pub(crate) id: Thing,
+    //Goes up with every write, so a request working on an outdated copy of the game can't overwrite newer changes.
+    #[serde(default)]
+    pub(crate) version: u32,
     pub(crate) state: GameState,
+    #[serde(default)]
+    pub(crate) format: BattleFormat,
+    //SurrealDB stores integers as i64, so a u64 seed would not survive a round trip.
+    pub(crate) seed: u32,
+    //The index of a player is the side they are playing on.
+    pub(crate) players: Vec<Player>,
+    //Unix time in seconds until which both teams have to be submitted, set once the second player joins.
+    #[serde(default)]
+    pub(crate) team_deadline: Option<i64>,
+    //Unix time in seconds at which the team preview ends, set once both teams are in.
+    pub(crate) preview_deadline: Option<i64>,
+    pub(crate) battle: Option<Battle>,
+    pub(crate) starting_battle: Option<Battle>,
 }
 
 impl Default for Game {
     fn default() -> Self {
         Game {
             id: Thing::from(("games", Id::rand())),
+            version: 0,
             state: GameState::Pending,
+            format: BattleFormat::Singles,
+            seed: Uuid::new_v4().as_fields().0,
+            players: Vec::new(),
+            team_deadline: None,
+            preview_deadline: None,
+            battle: None,
+            starting_battle: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct Player {
+    pub(crate) id: String,
+    pub(crate) team: Option<Vec<CreatureSet>>,
+    //Indices into the team of the creatures brought into the battle, the first ones lead.
+    pub(crate) lead_order: Option<Vec<usize>>,
+}
+
+impl Game {
+    pub fn add_player(&mut self) -> String {
+        let id = Uuid::new_v4().to_string();
+        self.players.push(Player {
+            id: id.clone(),
+            team: None,
+            lead_order: None,
+        });
+        id
+    }
+
+    //The team preview starts as soon as the second player is in, both teams have to be submitted before the deadline.
+    pub fn join(&mut self, dex: &Dex, now: i64) -> String {
+        let player_id = self.add_player();
+        self.state = GameState::TeamPreview;
+        self.team_deadline = Some(now + dex.formats.get(self.format).team_seconds as i64);
+        player_id
+    }
+
+    pub fn teams_submitted(&self) -> bool {
+        self.players.len() == 2 && self.players.iter().all(|player| player.team.is_some())
+    }
+
+    pub fn side_of(&self, player_id: &str) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.id == player_id)
+    }
+
+    //Both teams are shown to the players once they are in, which starts the time for picking the leads.
+    //Returns whether this was the second one.
+    pub fn submit_team(
+        &mut self,
+        dex: &Dex,
+        side: usize,
+        team: Vec<CreatureSet>,
+        now: i64,
+    ) -> Result<bool, BattleError> {
+        self.end_overdue_preview(dex, now);
+        match self.state {
+            GameState::Pending | GameState::TeamPreview => {}
+            GameState::Ongoing => {
+                return Err(BattleError::InvalidTeam(String::from(
+                    "The battle has already started.",
+                )))
+            }
+            GameState::Finished | GameState::Cancelled => return Err(BattleError::BattleOver),
+        }
+        if self.teams_submitted() {
+            return Err(BattleError::InvalidTeam(String::from(
+                "Both teams are already shown in the team preview.",
+            )));
+        }
+        let rules = dex.formats.get(self.format);
+        validate_team(dex, rules, &team).map_err(BattleError::RuleViolations)?;
+        self.players[side].team = Some(team);
+        if !self.teams_submitted() {
+            return Ok(false);
+        }
+        self.preview_deadline = Some(now + rules.preview_seconds as i64);
+        Ok(true)
+    }
+
+    //The battle starts once both players have picked which creatures they bring.
+    pub fn submit_lead_order(
+        &mut self,
+        dex: &Dex,
+        side: usize,
+        lead_order: Vec<usize>,
+        now: i64,
+    ) -> Result<Option<Vec<BattleEvent>>, BattleError> {
+        self.end_overdue_preview(dex, now);
+        if self.state != GameState::TeamPreview || !self.teams_submitted() {
+            return Err(BattleError::NotInPreview);
+        }
+        let player = &self.players[side];
+        if player.lead_order.is_some() {
+            return Err(BattleError::AlreadySubmitted);
+        }
+        let team_size = player.team.as_ref().map_or(0, Vec::len);
+        let bring = dex.formats.get(self.format).bring.min(team_size);
+        let invalid = lead_order.len() != bring
+            || lead_order
+                .iter()
+                .enumerate()
+                .any(|(picked, index)| *index >= team_size || lead_order[..picked].contains(index));
+        if invalid {
+            return Err(BattleError::InvalidTeam(format!(
+                "Pick {} different creatures of your team.",
+                bring
+            )));
+        }
+        self.players[side].lead_order = Some(lead_order);
+
+        if self
+            .players
+            .iter()
+            .all(|player| player.lead_order.is_some())
+        {
+            return Ok(Some(self.start_battle(dex)));
+        }
+        Ok(None)
+    }
+
+    //Nothing runs in the background, so every request applies the deadlines itself. Ending a preview
+    //always gives the same result, so it doesn't matter which request gets to write it.
+    //A game which still misses a team once its time is up gets cancelled, once both teams are in
+    //the battle starts when the time for picking the leads is up. Returns whether the game changed.
+    pub fn end_overdue_preview(&mut self, dex: &Dex, now: i64) -> bool {
+        if self.state != GameState::TeamPreview {
+            return false;
+        }
+        let overdue = |deadline: Option<i64>| deadline.is_some_and(|deadline| now >= deadline);
+        if self.teams_submitted() {
+            if !overdue(self.preview_deadline) {
+                return false;
+            }
+            self.start_battle(dex);
+        } else {
+            if !overdue(self.team_deadline) {
+                return false;
+            }
+            self.state = GameState::Cancelled;
+        }
+        true
+    }
+
+    //Players without a lead order bring the first creatures of their team.
+    fn brought_creatures(&self, dex: &Dex, side: usize) -> Vec<CreatureSet> {
+        let player = &self.players[side];
+        let team = player.team.as_deref().unwrap_or_default();
+        match &player.lead_order {
+            Some(lead_order) => lead_order
+                .iter()
+                .map(|index| team[*index].clone())
+                .collect(),
+            None => team
+                .iter()
+                .take(dex.formats.get(self.format).bring)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn start_battle(&mut self, dex: &Dex) -> Vec<BattleEvent> {
+        let teams = [
+            build_team(dex, &self.brought_creatures(dex, 0)),
+            build_team(dex, &self.brought_creatures(dex, 1)),
+        ];
+        let mut battle = Battle::new(self.format, teams, self.seed);
+        let events = battle.start(dex);
+        self.starting_battle = Some(battle.clone());
+        self.battle = Some(battle);
+        self.state = GameState::Ongoing;
+        events
+    }
+
+    pub fn submit_action(
+        &mut self,
+        dex: &Dex,
+        side: usize,
+        position: usize,
+        action: Action,
+        now: i64,
+    ) -> Result<Option<Vec<BattleEvent>>, BattleError> {
+        self.end_overdue_preview(dex, now);
+        match self.state {
+            GameState::Ongoing => {}
+            GameState::Pending | GameState::TeamPreview => return Err(BattleError::NotStarted),
+            GameState::Finished | GameState::Cancelled => return Err(BattleError::BattleOver),
+        }
+        let battle = self.battle.as_mut().ok_or(BattleError::NotStarted)?;
+        let events = battle.submit_action(dex, side, position, action)?;
+        if battle.is_over() {
+            self.state = GameState::Finished;
+        }
+        Ok(events)
+    }
+
+    pub fn resimulate(&self, dex: &Dex) -> Result<Battle, BattleError> {
+        let (Some(start), Some(battle)) = (&self.starting_battle, &self.battle) else {
+            return Err(BattleError::NotStarted);
+        };
+        Battle::replay(start, &battle.history, dex)
+    }
+}
+
+//A team saved by a player, so it doesn't have to be put together again for every game.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct Team {
+    pub(crate) id: Thing,
+    pub(crate) player_id: String,
+    pub(crate) name: String,
+    //The format the team is built for, its rules are checked whenever the team gets saved.
+    #[serde(default)]
+    pub(crate) format: BattleFormat,
+    pub(crate) creatures: Vec<CreatureSet>,
+}
+
+impl Team {
+    pub fn new(
+        player_id: &str,
+        name: &str,
+        format: BattleFormat,
+        creatures: Vec<CreatureSet>,
+    ) -> Self {
+        Team {
+            id: Thing::from(("teams", Id::rand())),
+            player_id: String::from(player_id),
+            name: String::from(name),
+            format,
+            creatures,
         }
     }
 }
@@ -19,7 +276,337 @@ impl Default for Game {
 #[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
 pub enum GameState {
     Pending,
+    //Both players are in and get to see the species of the other team before picking their leads.
+    TeamPreview,
     Ongoing,
     Finished,
     Cancelled,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::battle::{stats::Stats, team::CreatureSet, Action, BattleError, BattleFormat};
+    use crate::db::entities::{Game, GameState};
+    use crate::{dex::Dex, DATA_DIRECTORY};
+
+    fn set(species: &str, level: u8, move_id: &str) -> CreatureSet {
+        CreatureSet {
+            species: String::from(species),
+            nickname: None,
+            level,
+            ability: None,
+            item: None,
+            moves: vec![String::from(move_id)],
+            investment: Stats::default(),
+            nature: None,
+        }
+    }
+
+    fn started_game(dex: &Dex, first: CreatureSet, second: CreatureSet) -> Game {
+        let mut game = Game::default();
+        game.add_player();
+        game.join(dex, 0);
+        assert!(!game.submit_team(dex, 0, vec![first], 0).unwrap());
+        assert!(game.submit_team(dex, 1, vec![second], 0).unwrap());
+        assert!(game.battle.is_none());
+        assert!(game
+            .submit_lead_order(dex, 0, vec![0], 0)
+            .unwrap()
+            .is_none());
+        assert!(game
+            .submit_lead_order(dex, 1, vec![0], 0)
+            .unwrap()
+            .is_some());
+        game
+    }
+
+    #[test]
+    fn test_battle_starts_once_both_lead_orders_are_picked() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+
+        let game = started_game(
+            &dex,
+            set("tidepup", 50, "tackle"),
+            set("sproutle", 50, "tackle"),
+        );
+
+        assert!(game.battle.is_some());
+        assert_eq!(game.battle, game.starting_battle);
+        assert_eq!(game.state, GameState::Ongoing);
+    }
+
+    #[test]
+    fn test_battle_uses_the_format_of_the_game() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game {
+            format: BattleFormat::Doubles,
+            state: GameState::TeamPreview,
+            ..Game::default()
+        };
+        game.add_player();
+        game.add_player();
+        let team = vec![set("tidepup", 50, "tackle"), set("sproutle", 50, "tackle")];
+
+        game.submit_team(&dex, 0, team.clone(), 0).unwrap();
+        game.submit_team(&dex, 1, team, 0).unwrap();
+        game.submit_lead_order(&dex, 0, vec![1, 0], 0).unwrap();
+        game.submit_lead_order(&dex, 1, vec![0, 1], 0).unwrap();
+
+        let battle = game.battle.unwrap();
+        assert_eq!(battle.format, BattleFormat::Doubles);
+        assert_eq!(battle.sides[1].active, vec![0, 1]);
+        assert_eq!(battle.sides[0].team[0].species, "sproutle");
+    }
+
+    #[test]
+    fn test_overdue_previews_bring_the_first_creatures() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game {
+            format: BattleFormat::Doubles,
+            ..Game::default()
+        };
+        game.add_player();
+        game.join(&dex, 1000);
+        let team = vec![
+            set("tidepup", 50, "tackle"),
+            set("sproutle", 50, "tackle"),
+            set("cindercub", 50, "tackle"),
+            set("pebblon", 50, "tackle"),
+            set("plumbird", 50, "tackle"),
+        ];
+        game.submit_team(&dex, 0, team.clone(), 1000).unwrap();
+        game.submit_team(&dex, 1, team, 1000).unwrap();
+        let deadline = game.preview_deadline.unwrap();
+
+        assert!(game
+            .submit_lead_order(&dex, 0, vec![4, 3, 2, 1], deadline - 1)
+            .unwrap()
+            .is_none());
+        assert!(!game.end_overdue_preview(&dex, deadline - 1));
+        assert_eq!(
+            game.submit_lead_order(&dex, 1, vec![0, 1, 2, 3], deadline),
+            Err(BattleError::NotInPreview)
+        );
+
+        let battle = game.battle.unwrap();
+        let species = |side: usize| -> Vec<&str> {
+            battle.sides[side]
+                .team
+                .iter()
+                .map(|creature| creature.species.as_str())
+                .collect()
+        };
+        assert_eq!(
+            species(0),
+            vec!["plumbird", "pebblon", "cindercub", "sproutle"]
+        );
+        assert_eq!(
+            species(1),
+            vec!["tidepup", "sproutle", "cindercub", "pebblon"]
+        );
+        assert_eq!(game.state, GameState::Ongoing);
+    }
+
+    #[test]
+    fn test_the_team_deadline_starts_when_the_second_player_joins() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game::default();
+        game.add_player();
+
+        game.join(&dex, 1000);
+
+        assert_eq!(game.state, GameState::TeamPreview);
+        assert_eq!(
+            game.team_deadline,
+            Some(1000 + dex.formats.get(BattleFormat::Singles).team_seconds as i64)
+        );
+        assert_eq!(game.preview_deadline, None);
+    }
+
+    #[test]
+    fn test_the_lead_deadline_starts_once_both_teams_are_in() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let rules = dex.formats.get(BattleFormat::Singles);
+        let mut game = Game::default();
+        game.add_player();
+        game.join(&dex, 0);
+        let late = game.team_deadline.unwrap() - 1;
+        game.submit_team(&dex, 0, vec![set("tidepup", 50, "tackle")], 0)
+            .unwrap();
+
+        assert!(game
+            .submit_team(&dex, 1, vec![set("sproutle", 50, "tackle")], late)
+            .unwrap());
+        assert_eq!(
+            game.preview_deadline,
+            Some(late + rules.preview_seconds as i64)
+        );
+        assert!(!game.end_overdue_preview(&dex, late + 1));
+        assert_eq!(game.state, GameState::TeamPreview);
+        assert!(game.end_overdue_preview(&dex, late + rules.preview_seconds as i64));
+        assert_eq!(game.state, GameState::Ongoing);
+    }
+
+    #[test]
+    fn test_actions_end_an_overdue_preview() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game::default();
+        game.add_player();
+        game.join(&dex, 0);
+        game.submit_team(&dex, 0, vec![set("tidepup", 50, "tackle")], 0)
+            .unwrap();
+        game.submit_team(&dex, 1, vec![set("sproutle", 50, "tackle")], 0)
+            .unwrap();
+        let tackle = Action::Move {
+            slot: 0,
+            target: None,
+        };
+        let deadline = game.preview_deadline.unwrap();
+
+        assert_eq!(
+            game.submit_action(&dex, 0, 0, tackle, deadline - 1),
+            Err(BattleError::NotStarted)
+        );
+        assert!(game
+            .submit_action(&dex, 0, 0, tackle, deadline)
+            .unwrap()
+            .is_none());
+        assert_eq!(game.state, GameState::Ongoing);
+    }
+
+    #[test]
+    fn test_games_missing_a_team_at_the_deadline_get_cancelled() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game::default();
+        game.add_player();
+        game.join(&dex, 0);
+        game.submit_team(&dex, 0, vec![set("tidepup", 50, "tackle")], 0)
+            .unwrap();
+        let deadline = game.team_deadline.unwrap();
+
+        assert_eq!(
+            game.submit_team(&dex, 1, vec![set("sproutle", 50, "tackle")], deadline),
+            Err(BattleError::BattleOver)
+        );
+        assert_eq!(game.state, GameState::Cancelled);
+        assert!(game.battle.is_none());
+    }
+
+    #[test]
+    fn test_invalid_lead_orders_are_rejected() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = Game {
+            state: GameState::TeamPreview,
+            ..Game::default()
+        };
+        game.add_player();
+        game.add_player();
+        let team = vec![set("tidepup", 50, "tackle"), set("sproutle", 50, "tackle")];
+        game.submit_team(&dex, 0, team.clone(), 0).unwrap();
+
+        assert_eq!(
+            game.submit_lead_order(&dex, 0, vec![0, 1], 0),
+            Err(BattleError::NotInPreview)
+        );
+        game.submit_team(&dex, 1, team.clone(), 0).unwrap();
+        assert!(matches!(
+            game.submit_team(&dex, 1, team, 0),
+            Err(BattleError::InvalidTeam(_))
+        ));
+        for invalid in [vec![0], vec![0, 0], vec![0, 2]] {
+            assert!(matches!(
+                game.submit_lead_order(&dex, 0, invalid, 0),
+                Err(BattleError::InvalidTeam(_))
+            ));
+        }
+        game.submit_lead_order(&dex, 0, vec![1, 0], 0).unwrap();
+        assert_eq!(
+            game.submit_lead_order(&dex, 0, vec![0, 1], 0),
+            Err(BattleError::AlreadySubmitted)
+        );
+    }
+
+    #[test]
+    fn test_players_are_assigned_to_sides() {
+        let mut game = Game::default();
+        let host = game.add_player();
+        let guest = game.add_player();
+
+        assert_eq!(game.side_of(&host), Some(0));
+        assert_eq!(game.side_of(&guest), Some(1));
+        assert_eq!(game.side_of("stranger"), None);
+    }
+
+    #[test]
+    fn test_game_finishes_with_the_battle() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = started_game(
+            &dex,
+            set("ironox", 100, "close-combat"),
+            set("ironox", 5, "close-combat"),
+        );
+
+        game.submit_action(
+            &dex,
+            0,
+            0,
+            Action::Move {
+                slot: 0,
+                target: None,
+            },
+            0,
+        )
+        .unwrap();
+        game.submit_action(
+            &dex,
+            1,
+            0,
+            Action::Move {
+                slot: 0,
+                target: None,
+            },
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(game.state, GameState::Finished);
+    }
+
+    #[test]
+    fn test_resimulating_a_finished_game() {
+        let dex = Dex::load(DATA_DIRECTORY).unwrap();
+        let mut game = started_game(
+            &dex,
+            set("pebblon", 50, "rock-throw"),
+            set("stormhawk", 50, "spark"),
+        );
+        while game.state == GameState::Ongoing {
+            game.submit_action(
+                &dex,
+                0,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+                0,
+            )
+            .unwrap();
+            game.submit_action(
+                &dex,
+                1,
+                0,
+                Action::Move {
+                    slot: 0,
+                    target: None,
+                },
+                0,
+            )
+            .unwrap();
+        }
+
+        let resimulated = game.resimulate(&dex).unwrap();
+
+        assert_eq!(Some(&resimulated), game.battle.as_ref());
+    }
+}
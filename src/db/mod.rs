@@ -1,5 +1,7 @@
 pub mod entities;
-use crate::db::entities::Game;
+use crate::battle::BattleFormat;
+use crate::db::entities::{Game, Team};
+use crate::dex::Dex;
 use rocket::http::Status;
 #[cfg(test)]
 use surrealdb::opt::auth::Root;
@@ -11,6 +13,8 @@ use surrealdb::{
 #[cfg(not(test))]
 use surrealdb::opt::auth::Namespace;
 
+const MAX_ATTEMPTS: u32 = 3;
+
 pub struct DbConnection {
     pub conn: Surreal<Client>,
 }
@@ -47,15 +51,19 @@ impl DbConnection {
         Ok(DbConnection { conn: db })
     }
 
-    pub async fn create_game(&self) -> Result<String, DbError> {
-        let game = Game::default();
+    pub async fn create_game(&self, format: BattleFormat) -> Result<Game, DbError> {
+        let mut game = Game {
+            format,
+            ..Game::default()
+        };
+        game.add_player();
         let query_result: Option<Game> = self.conn.create("games").content(game).await?;
         match query_result {
             None => Err(DbError {
                 message: String::from("Couldn't create the lobby"),
                 status_code: Status::InternalServerError,
             }),
-            Some(created_game) => Ok(created_game.id.id.to_string()),
+            Some(created_game) => Ok(created_game),
         }
     }
 
@@ -70,15 +78,107 @@ impl DbConnection {
         }
     }
 
-    pub async fn update_game(&self, updated_game: Game) -> Result<(), DbError> {
-        let update_result: Option<Game> = self
+    //Both players of a game send requests at the same time, so the game is only written if nobody
+    //else wrote it since it was read. Otherwise one of the two changes would get lost.
+    pub async fn update_game(&self, mut updated_game: Game) -> Result<Game, DbError> {
+        let read_version = updated_game.version;
+        updated_game.version += 1;
+        let game_id = updated_game.id.id.to_string();
+        let mut response = self
+            .conn
+            .query("UPDATE $game CONTENT $content WHERE (version ?? 0) = $version")
+            .bind(("game", updated_game.id.clone()))
+            .bind(("version", read_version))
+            .bind(("content", updated_game))
+            .await?;
+        let update_result: Vec<Game> = response.take(0)?;
+        if let Some(game) = update_result.into_iter().next() {
+            return Ok(game);
+        }
+        //Tells a game that doesn't exist apart from one that was changed in the meantime.
+        self.get_game(&game_id).await?;
+        Err(DbError {
+            message: String::from(
+                "The game was changed by another request in the meantime, please try again.",
+            ),
+            status_code: Status::Conflict,
+        })
+    }
+
+    //Deadlines pass without anybody sending a request, so the game gets read with the overdue ones
+    //applied and saved. Another request might save the same result first, which is just tried again.
+    pub async fn get_current_game(
+        &self,
+        game_id: &str,
+        dex: &Dex,
+        now: i64,
+    ) -> Result<Game, DbError> {
+        let mut attempts = 1;
+        loop {
+            let mut game = self.get_game(game_id).await?;
+            if !game.end_overdue_preview(dex, now) {
+                return Ok(game);
+            }
+            match self.update_game(game).await {
+                Err(err) if err.status_code == Status::Conflict && attempts < MAX_ATTEMPTS => {
+                    attempts += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub async fn create_team(&self, team: Team) -> Result<Team, DbError> {
+        let query_result: Option<Team> = self.conn.create("teams").content(team).await?;
+        match query_result {
+            None => Err(DbError {
+                message: String::from("Couldn't create the team"),
+                status_code: Status::InternalServerError,
+            }),
+            Some(created_team) => Ok(created_team),
+        }
+    }
+
+    pub async fn get_team(&self, team_id: &str) -> Result<Team, DbError> {
+        let query_result: Option<Team> = self.conn.select(("teams", team_id)).await?;
+        match query_result {
+            None => Err(DbError {
+                message: String::from("Couldn't find the team you're looking for."),
+                status_code: Status::NotFound,
+            }),
+            Some(team) => Ok(team),
+        }
+    }
+
+    pub async fn list_teams(&self, player_id: &str) -> Result<Vec<Team>, DbError> {
+        let mut response = self
+            .conn
+            .query("SELECT * FROM teams WHERE player_id = $player_id")
+            .bind(("player_id", String::from(player_id)))
+            .await?;
+        Ok(response.take(0)?)
+    }
+
+    pub async fn update_team(&self, updated_team: Team) -> Result<(), DbError> {
+        let update_result: Option<Team> = self
             .conn
-            .update(("games", updated_game.id.id.to_string()))
-            .content(updated_game)
+            .update(("teams", updated_team.id.id.to_string()))
+            .content(updated_team)
             .await?;
         match update_result {
             None => Err(DbError {
-                message: String::from("Couldn't find the game you're looking for."),
+                message: String::from("Couldn't find the team you're looking for."),
+                status_code: Status::NotFound,
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+
+    pub async fn delete_team(&self, team_id: &str) -> Result<(), DbError> {
+        let delete_result: Option<Team> = self.conn.delete(("teams", team_id)).await?;
+        match delete_result {
+            None => Err(DbError {
+                message: String::from("Couldn't find the team you're looking for."),
                 status_code: Status::NotFound,
             }),
             Some(_) => Ok(()),
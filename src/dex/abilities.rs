@@ -0,0 +1,13 @@
+use rocket::serde::{json::serde_json::Value, Deserialize, Serialize};
+
+//The handler names one of the behaviours implemented in battle::abilities,
+//the parameters tune it. New abilities reusing an existing behaviour only need a new entry.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Ability {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) handler: String,
+    #[serde(default)]
+    pub(crate) parameters: Value,
+}
@@ -0,0 +1,221 @@
+use crate::dex::{moves::StatusKind, statuses::Fraction, DexError};
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum WeatherKind {
+    Sun,
+    Rain,
+    Sandstorm,
+    Hail,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum TerrainKind {
+    Electric,
+    Grassy,
+    Psychic,
+    Misty,
+}
+
+impl WeatherKind {
+    pub const ALL: [WeatherKind; 4] = [
+        WeatherKind::Sun,
+        WeatherKind::Rain,
+        WeatherKind::Sandstorm,
+        WeatherKind::Hail,
+    ];
+}
+
+impl TerrainKind {
+    pub const ALL: [TerrainKind; 4] = [
+        TerrainKind::Electric,
+        TerrainKind::Grassy,
+        TerrainKind::Psychic,
+        TerrainKind::Misty,
+    ];
+}
+
+//Both weather and terrain affect the whole field and wear off after a fixed number of turns.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct FieldData {
+    pub(crate) weather: Vec<WeatherCondition>,
+    pub(crate) terrain: Vec<TerrainCondition>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct WeatherCondition {
+    pub(crate) kind: WeatherKind,
+    pub(crate) duration: u8,
+    pub(crate) power_modifiers: Vec<TypeModifier>,
+    pub(crate) end_of_turn_damage: Option<Fraction>,
+    //Creatures of these types don't take the end of turn damage.
+    pub(crate) immune_types: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct TerrainCondition {
+    pub(crate) kind: TerrainKind,
+    pub(crate) duration: u8,
+    pub(crate) power_modifiers: Vec<TypeModifier>,
+    pub(crate) end_of_turn_heal: Option<Fraction>,
+    pub(crate) blocked_statuses: Vec<StatusKind>,
+    //Moves with a raised priority fail against the creatures on the field.
+    pub(crate) blocks_priority: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct TypeModifier {
+    pub(crate) move_type: String,
+    pub(crate) multiplier: f32,
+}
+
+pub struct FieldRegistry {
+    weather: Vec<WeatherCondition>,
+    terrain: Vec<TerrainCondition>,
+}
+
+fn power_modifier(modifiers: &[TypeModifier], move_type: &str) -> f32 {
+    modifiers
+        .iter()
+        .filter(|modifier| modifier.move_type == move_type)
+        .map(|modifier| modifier.multiplier)
+        .product()
+}
+
+impl WeatherCondition {
+    pub fn power_modifier(&self, move_type: &str) -> f32 {
+        power_modifier(&self.power_modifiers, move_type)
+    }
+}
+
+impl TerrainCondition {
+    pub fn power_modifier(&self, move_type: &str) -> f32 {
+        power_modifier(&self.power_modifiers, move_type)
+    }
+}
+
+impl FieldRegistry {
+    pub fn new(data: FieldData) -> Result<Self, DexError> {
+        for kind in WeatherKind::ALL {
+            if data
+                .weather
+                .iter()
+                .filter(|weather| weather.kind == kind)
+                .count()
+                != 1
+            {
+                return Err(DexError {
+                    message: format!("Weather {:?} has to be defined exactly once.", kind),
+                });
+            }
+        }
+        for kind in TerrainKind::ALL {
+            if data
+                .terrain
+                .iter()
+                .filter(|terrain| terrain.kind == kind)
+                .count()
+                != 1
+            {
+                return Err(DexError {
+                    message: format!("Terrain {:?} has to be defined exactly once.", kind),
+                });
+            }
+        }
+        for weather in &data.weather {
+            if weather.duration == 0
+                || weather
+                    .end_of_turn_damage
+                    .is_some_and(|damage| damage.denominator == 0)
+            {
+                return Err(DexError {
+                    message: format!("Weather {:?} is invalid.", weather.kind),
+                });
+            }
+        }
+        for terrain in &data.terrain {
+            if terrain.duration == 0
+                || terrain
+                    .end_of_turn_heal
+                    .is_some_and(|heal| heal.denominator == 0)
+            {
+                return Err(DexError {
+                    message: format!("Terrain {:?} is invalid.", terrain.kind),
+                });
+            }
+        }
+        Ok(FieldRegistry {
+            weather: data.weather,
+            terrain: data.terrain,
+        })
+    }
+
+    //Every kind is guaranteed to exist, the constructor checks it.
+    pub fn weather(&self, kind: WeatherKind) -> &WeatherCondition {
+        self.weather
+            .iter()
+            .find(|weather| weather.kind == kind)
+            .expect("Every weather kind is validated on load.")
+    }
+
+    pub fn terrain(&self, kind: TerrainKind) -> &TerrainCondition {
+        self.terrain
+            .iter()
+            .find(|terrain| terrain.kind == kind)
+            .expect("Every terrain kind is validated on load.")
+    }
+
+    //All the move types mentioned anywhere, so the dex can check that they exist.
+    pub fn referenced_types(&self) -> impl Iterator<Item = &String> {
+        self.weather
+            .iter()
+            .flat_map(|weather| {
+                weather
+                    .power_modifiers
+                    .iter()
+                    .map(|modifier| &modifier.move_type)
+                    .chain(&weather.immune_types)
+            })
+            .chain(self.terrain.iter().flat_map(|terrain| {
+                terrain
+                    .power_modifiers
+                    .iter()
+                    .map(|modifier| &modifier.move_type)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::fields::{FieldData, FieldRegistry, TerrainKind, WeatherKind};
+    use crate::dex::{moves::StatusKind, read_data_file};
+
+    #[test]
+    fn test_loading_shipped_fields() {
+        let data: FieldData = read_data_file("data", "fields.json").unwrap();
+        let fields = FieldRegistry::new(data).expect("The shipped field data should be valid.");
+
+        assert_eq!(fields.weather(WeatherKind::Sun).power_modifier("fire"), 1.5);
+        assert_eq!(
+            fields.weather(WeatherKind::Sun).power_modifier("water"),
+            0.5
+        );
+        assert_eq!(
+            fields.weather(WeatherKind::Sun).power_modifier("grass"),
+            1.0
+        );
+        assert!(fields
+            .terrain(TerrainKind::Misty)
+            .blocked_statuses
+            .contains(&StatusKind::Burn));
+    }
+
+    #[test]
+    fn test_missing_weather_is_rejected() {
+        let mut data: FieldData = read_data_file("data", "fields.json").unwrap();
+        data.weather
+            .retain(|weather| weather.kind != WeatherKind::Hail);
+
+        assert!(FieldRegistry::new(data).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::battle::{team::MAX_TEAM_SIZE, BattleFormat};
+use crate::dex::DexError;
+use rocket::serde::{Deserialize, Serialize};
+
+//The rules a team has to follow to be brought into a game of the format.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct FormatRules {
+    pub(crate) format: BattleFormat,
+    pub(crate) level_cap: u8,
+    pub(crate) max_stat_investment: u16,
+    pub(crate) max_total_investment: u16,
+    //No two creatures of the same species.
+    pub(crate) species_clause: bool,
+    //No two creatures holding the same item.
+    pub(crate) item_clause: bool,
+    //How many creatures of their team each player picks during the team preview.
+    pub(crate) bring: usize,
+    //How long both players have to submit their team once the second one joined.
+    //A game still missing a team by then gets cancelled.
+    pub(crate) team_seconds: u32,
+    //How long the players have to pick their leads once both teams are in.
+    //Players who haven't picked once the time is up bring the first creatures of their team.
+    pub(crate) preview_seconds: u32,
+}
+
+pub struct FormatRegistry {
+    formats: Vec<FormatRules>,
+}
+
+impl FormatRegistry {
+    pub fn new(formats: Vec<FormatRules>) -> Result<Self, DexError> {
+        for format in BattleFormat::ALL {
+            let count = formats
+                .iter()
+                .filter(|rules| rules.format == format)
+                .count();
+            if count != 1 {
+                return Err(DexError {
+                    message: format!("Format {:?} has to be defined exactly once.", format),
+                });
+            }
+        }
+        for rules in &formats {
+            if rules.level_cap == 0 {
+                return Err(DexError {
+                    message: format!(
+                        "The level cap of format {:?} has to be at least 1.",
+                        rules.format
+                    ),
+                });
+            }
+            if rules.bring < rules.format.active_per_side() || rules.bring > MAX_TEAM_SIZE {
+                return Err(DexError {
+                    message: format!(
+                        "Format {:?} has to bring between {} and {} creatures.",
+                        rules.format,
+                        rules.format.active_per_side(),
+                        MAX_TEAM_SIZE
+                    ),
+                });
+            }
+            if rules.team_seconds == 0 {
+                return Err(DexError {
+                    message: format!("Format {:?} needs time for submitting teams.", rules.format),
+                });
+            }
+            if rules.preview_seconds == 0 {
+                return Err(DexError {
+                    message: format!("Format {:?} needs time for the team preview.", rules.format),
+                });
+            }
+        }
+        Ok(FormatRegistry { formats })
+    }
+
+    //Every format is guaranteed to exist, the constructor checks it.
+    pub fn get(&self, format: BattleFormat) -> &FormatRules {
+        self.formats
+            .iter()
+            .find(|rules| rules.format == format)
+            .expect("Every format is validated on load.")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::BattleFormat;
+    use crate::dex::formats::FormatRegistry;
+    use crate::dex::Dex;
+
+    #[test]
+    fn test_every_format_needs_rules() {
+        let dex = Dex::load("data").expect("The shipped formats should be valid.");
+        let singles = dex.formats.get(BattleFormat::Singles).clone();
+        let mut uncapped = singles.clone();
+        uncapped.level_cap = 0;
+        let mut bringing_nothing = singles.clone();
+        bringing_nothing.bring = 0;
+
+        assert!(dex.formats.get(BattleFormat::Doubles).item_clause);
+        assert!(FormatRegistry::new(vec![singles.clone()]).is_err());
+        for invalid in [uncapped, bringing_nothing] {
+            assert!(FormatRegistry::new(vec![
+                invalid,
+                dex.formats.get(BattleFormat::Doubles).clone()
+            ])
+            .is_err());
+        }
+    }
+}
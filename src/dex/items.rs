@@ -0,0 +1,12 @@
+use rocket::serde::{json::serde_json::Value, Deserialize, Serialize};
+
+//Works just like an ability, the handler names one of the behaviours implemented in battle::items.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Item {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) handler: String,
+    #[serde(default)]
+    pub(crate) parameters: Value,
+}
@@ -0,0 +1,76 @@
+use crate::dex::DexError;
+use rocket::serde::{Deserialize, Serialize};
+
+//Rules of the battle engine itself which aren't tied to any single move, creature or effect.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Mechanics {
+    pub(crate) critical_hit: CriticalHitRules,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct CriticalHitRules {
+    pub(crate) multiplier: f32,
+    //Critical hits happen with a chance of 1 in the entry for the stage, higher stages use the last entry.
+    pub(crate) chances: Vec<u32>,
+}
+
+impl Mechanics {
+    pub fn validate(&self) -> Result<(), DexError> {
+        let critical_hit = &self.critical_hit;
+        if critical_hit.multiplier < 1.0 {
+            return Err(DexError {
+                message: String::from("The critical hit multiplier is below 1."),
+            });
+        }
+        if critical_hit.chances.is_empty() || critical_hit.chances.contains(&0) {
+            return Err(DexError {
+                message: String::from("Every critical hit stage needs a chance of at least 1."),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl CriticalHitRules {
+    pub fn chance(&self, stage: u8) -> u32 {
+        let last = self.chances.len() - 1;
+        self.chances[(stage as usize).min(last)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::mechanics::{CriticalHitRules, Mechanics};
+    use crate::dex::Dex;
+
+    fn mechanics(multiplier: f32, chances: Vec<u32>) -> Mechanics {
+        Mechanics {
+            critical_hit: CriticalHitRules {
+                multiplier,
+                chances,
+            },
+        }
+    }
+
+    #[test]
+    fn test_shipped_mechanics_are_valid() {
+        let dex = Dex::load("data").expect("The shipped mechanics should be valid.");
+
+        assert_eq!(dex.mechanics.critical_hit.chance(0), 24);
+    }
+
+    #[test]
+    fn test_stages_past_the_last_chance_use_the_last_one() {
+        let mechanics = mechanics(1.5, vec![24, 8, 2]);
+
+        assert_eq!(mechanics.critical_hit.chance(1), 8);
+        assert_eq!(mechanics.critical_hit.chance(6), 2);
+    }
+
+    #[test]
+    fn test_invalid_critical_hit_rules() {
+        assert!(mechanics(0.5, vec![24]).validate().is_err());
+        assert!(mechanics(1.5, Vec::new()).validate().is_err());
+        assert!(mechanics(1.5, vec![24, 0]).validate().is_err());
+    }
+}
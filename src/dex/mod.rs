@@ -0,0 +1,173 @@
+pub mod abilities;
+pub mod fields;
+pub mod formats;
+pub mod items;
+pub mod mechanics;
+pub mod moves;
+pub mod side_conditions;
+pub mod species;
+pub mod statuses;
+pub mod types;
+
+use crate::battle::{abilities::AbilityRegistry, items::ItemRegistry};
+use crate::dex::{
+    abilities::Ability,
+    fields::{FieldData, FieldRegistry},
+    formats::{FormatRegistry, FormatRules},
+    items::Item,
+    mechanics::Mechanics,
+    moves::{Move, MoveRegistry},
+    side_conditions::{SideCondition, SideConditionRegistry},
+    species::{Species, SpeciesRegistry},
+    statuses::{StatusCondition, StatusRegistry},
+    types::TypeChart,
+};
+use rocket::serde::{de::DeserializeOwned, json::serde_json};
+use std::{fs, path::Path};
+
+//All game data lives in plain files, so balance changes don't require touching any code.
+pub struct Dex {
+    pub species: SpeciesRegistry,
+    pub moves: MoveRegistry,
+    pub statuses: StatusRegistry,
+    pub fields: FieldRegistry,
+    pub side_conditions: SideConditionRegistry,
+    pub abilities: AbilityRegistry,
+    pub items: ItemRegistry,
+    pub types: TypeChart,
+    pub mechanics: Mechanics,
+    pub formats: FormatRegistry,
+}
+
+impl Dex {
+    pub fn load(directory: &str) -> Result<Self, DexError> {
+        let species: Vec<Species> = read_data_file(directory, "species.json")?;
+        let moves: Vec<Move> = read_data_file(directory, "moves.json")?;
+        let statuses: Vec<StatusCondition> = read_data_file(directory, "statuses.json")?;
+        let fields: FieldData = read_data_file(directory, "fields.json")?;
+        let side_conditions: Vec<SideCondition> =
+            read_data_file(directory, "side_conditions.json")?;
+        let abilities: Vec<Ability> = read_data_file(directory, "abilities.json")?;
+        let items: Vec<Item> = read_data_file(directory, "items.json")?;
+        let types: TypeChart = read_data_file(directory, "type_chart.json")?;
+        types.validate()?;
+        let mechanics: Mechanics = read_data_file(directory, "mechanics.json")?;
+        mechanics.validate()?;
+        let formats: Vec<FormatRules> = read_data_file(directory, "formats.json")?;
+        let dex = Dex {
+            species: SpeciesRegistry::new(species)?,
+            moves: MoveRegistry::new(moves)?,
+            statuses: StatusRegistry::new(statuses)?,
+            fields: FieldRegistry::new(fields)?,
+            side_conditions: SideConditionRegistry::new(side_conditions)?,
+            abilities: AbilityRegistry::new(abilities)?,
+            items: ItemRegistry::new(items)?,
+            types,
+            mechanics,
+            formats: FormatRegistry::new(formats)?,
+        };
+        dex.check_references()?;
+        Ok(dex)
+    }
+
+    //The registries are loaded independently, so ids pointing into other registries are checked afterward.
+    fn check_references(&self) -> Result<(), DexError> {
+        for species in self.species.all() {
+            if let Some(unknown) = species
+                .types
+                .iter()
+                .find(|type_id| !self.types.contains(type_id))
+            {
+                return Err(DexError {
+                    message: format!("Species {} has the unknown type {}.", species.id, unknown),
+                });
+            }
+            if let Some(unknown) = species
+                .abilities
+                .iter()
+                .find(|ability_id| self.abilities.get(ability_id).is_none())
+            {
+                return Err(DexError {
+                    message: format!(
+                        "Species {} has the unknown ability {}.",
+                        species.id, unknown
+                    ),
+                });
+            }
+            if let Some(unknown) = species
+                .learnset
+                .iter()
+                .find(|move_id| self.moves.get(move_id).is_none())
+            {
+                return Err(DexError {
+                    message: format!(
+                        "Species {} can learn the unknown move {}.",
+                        species.id, unknown
+                    ),
+                });
+            }
+        }
+        for known_move in self.moves.all() {
+            if !self.types.contains(&known_move.move_type) {
+                return Err(DexError {
+                    message: format!(
+                        "Move {} has the unknown type {}.",
+                        known_move.id, known_move.move_type
+                    ),
+                });
+            }
+        }
+        for status in self.statuses.all() {
+            if let Some(unknown) = status
+                .immune_types
+                .iter()
+                .chain(&status.cured_by_move_types)
+                .find(|type_id| !self.types.contains(type_id))
+            {
+                return Err(DexError {
+                    message: format!("Status {:?} has the unknown type {}.", status.kind, unknown),
+                });
+            }
+        }
+        if let Some(unknown) = self
+            .fields
+            .referenced_types()
+            .find(|type_id| !self.types.contains(type_id))
+        {
+            return Err(DexError {
+                message: format!("The field conditions use the unknown type {}.", unknown),
+            });
+        }
+        for condition in self.side_conditions.all() {
+            if let Some(unknown) = condition
+                .immune_types
+                .iter()
+                .chain(&condition.entry_damage_type)
+                .find(|type_id| !self.types.contains(type_id))
+            {
+                return Err(DexError {
+                    message: format!(
+                        "Side condition {:?} has the unknown type {}.",
+                        condition.kind, unknown
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_data_file<T: DeserializeOwned>(directory: &str, file_name: &str) -> Result<T, DexError> {
+    let path = Path::new(directory).join(file_name);
+    let content = fs::read_to_string(&path).map_err(|err| DexError {
+        message: format!("Couldn't read {}: {}", path.display(), err),
+    })?;
+    serde_json::from_str(&content).map_err(|err| DexError {
+        message: format!("Couldn't parse {}: {}", path.display(), err),
+    })
+}
+
+#[derive(Debug)]
+pub struct DexError {
+    pub message: String,
+}
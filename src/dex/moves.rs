@@ -0,0 +1,309 @@
+use crate::dex::{
+    fields::{TerrainKind, WeatherKind},
+    side_conditions::SideConditionKind,
+    statuses::Fraction,
+    DexError,
+};
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Move {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) move_type: String,
+    pub(crate) category: MoveCategory,
+    pub(crate) power: Option<u16>,
+    //Moves without an accuracy never miss.
+    pub(crate) accuracy: Option<u8>,
+    pub(crate) pp: u8,
+    pub(crate) priority: i8,
+    //Moves with a high critical hit ratio start at a higher stage.
+    #[serde(default)]
+    pub(crate) critical_stage: u8,
+    pub(crate) target: MoveTarget,
+    //Moves without a hit count hit once.
+    #[serde(default)]
+    pub(crate) hits: Option<HitCount>,
+    //Replaces the regular damage calculation, so the power of the move doesn't matter.
+    #[serde(default)]
+    pub(crate) fixed_damage: Option<FixedDamage>,
+    //Both are fractions of the total damage dealt to the target.
+    #[serde(default)]
+    pub(crate) recoil: Option<Fraction>,
+    #[serde(default)]
+    pub(crate) drain: Option<Fraction>,
+    #[serde(default)]
+    pub(crate) charge: Option<Charge>,
+    //The user has to skip its next turn to recharge after using the move.
+    #[serde(default)]
+    pub(crate) recharge: bool,
+    pub(crate) secondary_effects: Vec<SecondaryEffect>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum MoveTarget {
+    User,
+    SingleFoe,
+    SingleAlly,
+    AllFoes,
+    AllAdjacent,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum HitCount {
+    Fixed(u8),
+    //Every number of hits between min and max is equally likely.
+    Random { min: u8, max: u8 },
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum FixedDamage {
+    Amount(u16),
+    //Deals as much damage as the level of the user.
+    Level,
+    //Knocks out the target in one hit, but fails against targets with a higher level.
+    OneHitKnockOut,
+}
+
+//Moves spending their first turn charging, they only hit on the second one.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Charge {
+    //Moves can't hit the user while it is charging, like when it flies up high.
+    #[serde(default)]
+    pub(crate) semi_invulnerable: bool,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub struct SecondaryEffect {
+    pub(crate) chance: u8,
+    pub(crate) effect: Effect,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
+pub enum Effect {
+    Status(StatusKind),
+    StatStage {
+        stat: BoostableStat,
+        stages: i8,
+        target: EffectTarget,
+    },
+    Flinch,
+    Confusion,
+    Protect,
+    //Draws the single target moves of the foes to the user for the rest of the turn.
+    Redirect,
+    Taunt,
+    LeechSeed,
+    Weather(WeatherKind),
+    Terrain(TerrainKind),
+    SideCondition {
+        kind: SideConditionKind,
+        target: EffectTarget,
+    },
+    ClearSideConditions {
+        kinds: Vec<SideConditionKind>,
+        target: EffectTarget,
+    },
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum StatusKind {
+    Burn,
+    Poison,
+    Paralysis,
+    Sleep,
+    Freeze,
+}
+
+impl StatusKind {
+    pub const ALL: [StatusKind; 5] = [
+        StatusKind::Burn,
+        StatusKind::Poison,
+        StatusKind::Paralysis,
+        StatusKind::Sleep,
+        StatusKind::Freeze,
+    ];
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum BoostableStat {
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+    Accuracy,
+    Evasion,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum EffectTarget {
+    User,
+    Target,
+}
+
+pub struct MoveRegistry {
+    moves: BTreeMap<String, Move>,
+}
+
+impl MoveRegistry {
+    pub fn new(moves: Vec<Move>) -> Result<Self, DexError> {
+        let mut registry = BTreeMap::new();
+        for entry in moves {
+            if entry.category != MoveCategory::Status
+                && entry.power.is_none()
+                && entry.fixed_damage.is_none()
+            {
+                return Err(DexError {
+                    message: format!("Damaging move {} has no power.", entry.id),
+                });
+            }
+            if entry.accuracy.is_some_and(|accuracy| accuracy > 100) {
+                return Err(DexError {
+                    message: format!("Move {} has an accuracy above 100.", entry.id),
+                });
+            }
+            if entry.pp == 0 {
+                return Err(DexError {
+                    message: format!("Move {} has no PP.", entry.id),
+                });
+            }
+            if entry
+                .secondary_effects
+                .iter()
+                .any(|secondary| secondary.chance == 0 || secondary.chance > 100)
+            {
+                return Err(DexError {
+                    message: format!(
+                        "Move {} has a secondary effect chance outside 1-100.",
+                        entry.id
+                    ),
+                });
+            }
+            let invalid_hits = match entry.hits {
+                Some(HitCount::Fixed(hits)) => hits == 0,
+                Some(HitCount::Random { min, max }) => min == 0 || min > max,
+                None => false,
+            };
+            if invalid_hits {
+                return Err(DexError {
+                    message: format!("Move {} has an invalid hit count.", entry.id),
+                });
+            }
+            if entry
+                .recoil
+                .iter()
+                .chain(&entry.drain)
+                .any(|fraction| fraction.denominator == 0)
+            {
+                return Err(DexError {
+                    message: format!(
+                        "Move {} has a recoil or drain without a denominator.",
+                        entry.id
+                    ),
+                });
+            }
+            if registry.contains_key(&entry.id) {
+                return Err(DexError {
+                    message: format!("Move {} is defined more than once.", entry.id),
+                });
+            }
+            registry.insert(entry.id.clone(), entry);
+        }
+        Ok(MoveRegistry { moves: registry })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Move> {
+        self.moves.get(id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &Move> {
+        self.moves.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::moves::{
+        Effect, FixedDamage, HitCount, MoveCategory, MoveRegistry, StatusKind,
+    };
+    use crate::dex::Dex;
+
+    #[test]
+    fn test_loading_shipped_moves() {
+        let dex = Dex::load("data").expect("The shipped move data should be valid.");
+
+        let body_slam = dex.moves.get("body-slam").unwrap();
+
+        assert_eq!(body_slam.category, MoveCategory::Physical);
+        assert_eq!(body_slam.power, Some(85));
+        assert_eq!(
+            body_slam.secondary_effects[0].effect,
+            Effect::Status(StatusKind::Paralysis)
+        );
+    }
+
+    #[test]
+    fn test_damaging_moves_need_power() {
+        let mut powerless = Dex::load("data")
+            .unwrap()
+            .moves
+            .get("tackle")
+            .unwrap()
+            .clone();
+        powerless.power = None;
+
+        assert!(MoveRegistry::new(vec![powerless]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_damage_moves_need_no_power() {
+        let mut fixed = Dex::load("data")
+            .unwrap()
+            .moves
+            .get("tackle")
+            .unwrap()
+            .clone();
+        fixed.power = None;
+        fixed.fixed_damage = Some(FixedDamage::Level);
+
+        assert!(MoveRegistry::new(vec![fixed]).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_hit_counts_are_rejected() {
+        let tackle = Dex::load("data")
+            .unwrap()
+            .moves
+            .get("tackle")
+            .unwrap()
+            .clone();
+
+        for hits in [HitCount::Fixed(0), HitCount::Random { min: 5, max: 2 }] {
+            let mut invalid = tackle.clone();
+            invalid.hits = Some(hits);
+            assert!(MoveRegistry::new(vec![invalid]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_duplicate_moves_are_rejected() {
+        let tackle = Dex::load("data")
+            .unwrap()
+            .moves
+            .get("tackle")
+            .unwrap()
+            .clone();
+
+        assert!(MoveRegistry::new(vec![tackle.clone(), tackle]).is_err());
+    }
+}
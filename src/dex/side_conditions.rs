@@ -0,0 +1,132 @@
+use crate::dex::{moves::MoveCategory, statuses::Fraction, DexError};
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub enum SideConditionKind {
+    Spikes,
+    StealthRock,
+    Reflect,
+    LightScreen,
+}
+
+impl SideConditionKind {
+    pub const ALL: [SideConditionKind; 4] = [
+        SideConditionKind::Spikes,
+        SideConditionKind::StealthRock,
+        SideConditionKind::Reflect,
+        SideConditionKind::LightScreen,
+    ];
+}
+
+//Effects bound to one side of the field instead of a single creature, like entry hazards and screens.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct SideCondition {
+    pub(crate) kind: SideConditionKind,
+    //Screens wear off after a number of turns, hazards stay until they get cleared.
+    pub(crate) duration: Option<u8>,
+    //Damage dealt to every creature entering the side, one entry per layer.
+    //The condition can be stacked as often as there are entries.
+    pub(crate) entry_damage: Vec<Fraction>,
+    //Scales the entry damage with the effectiveness of this type against the creature.
+    pub(crate) entry_damage_type: Option<String>,
+    pub(crate) immune_types: Vec<String>,
+    pub(crate) screen: Option<Screen>,
+}
+
+//Multiplies the damage the side takes from moves of one category.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub struct Screen {
+    pub(crate) category: MoveCategory,
+    pub(crate) multiplier: f32,
+}
+
+impl SideCondition {
+    pub fn max_layers(&self) -> u8 {
+        self.entry_damage.len().max(1) as u8
+    }
+}
+
+pub struct SideConditionRegistry {
+    conditions: Vec<SideCondition>,
+}
+
+impl SideConditionRegistry {
+    pub fn new(conditions: Vec<SideCondition>) -> Result<Self, DexError> {
+        for kind in SideConditionKind::ALL {
+            let count = conditions
+                .iter()
+                .filter(|condition| condition.kind == kind)
+                .count();
+            if count != 1 {
+                return Err(DexError {
+                    message: format!("Side condition {:?} has to be defined exactly once.", kind),
+                });
+            }
+        }
+        for condition in &conditions {
+            if condition.duration == Some(0)
+                || condition
+                    .entry_damage
+                    .iter()
+                    .any(|damage| damage.denominator == 0)
+                || condition
+                    .screen
+                    .is_some_and(|screen| screen.multiplier < 0.0)
+            {
+                return Err(DexError {
+                    message: format!("Side condition {:?} is invalid.", condition.kind),
+                });
+            }
+        }
+        Ok(SideConditionRegistry { conditions })
+    }
+
+    //Every kind is guaranteed to exist, the constructor checks it.
+    pub fn get(&self, kind: SideConditionKind) -> &SideCondition {
+        self.conditions
+            .iter()
+            .find(|condition| condition.kind == kind)
+            .expect("Every side condition kind is validated on load.")
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &SideCondition> {
+        self.conditions.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::side_conditions::{SideConditionKind, SideConditionRegistry};
+    use crate::dex::Dex;
+
+    #[test]
+    fn test_loading_shipped_side_conditions() {
+        let dex = Dex::load("data").expect("The shipped side condition data should be valid.");
+
+        assert_eq!(
+            dex.side_conditions
+                .get(SideConditionKind::Spikes)
+                .max_layers(),
+            3
+        );
+        assert_eq!(
+            dex.side_conditions
+                .get(SideConditionKind::Reflect)
+                .max_layers(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_missing_side_conditions_are_rejected() {
+        let dex = Dex::load("data").unwrap();
+        let conditions = dex
+            .side_conditions
+            .all()
+            .filter(|condition| condition.kind != SideConditionKind::Reflect)
+            .cloned()
+            .collect();
+
+        assert!(SideConditionRegistry::new(conditions).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{battle::stats::Stats, dex::DexError};
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Species {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) types: Vec<String>,
+    pub(crate) base_stats: Stats,
+    pub(crate) abilities: Vec<String>,
+    pub(crate) learnset: Vec<String>,
+}
+
+pub struct SpeciesRegistry {
+    species: BTreeMap<String, Species>,
+}
+
+impl SpeciesRegistry {
+    pub fn new(species: Vec<Species>) -> Result<Self, DexError> {
+        let mut registry = BTreeMap::new();
+        for entry in species {
+            if entry.types.is_empty() || entry.types.len() > 2 {
+                return Err(DexError {
+                    message: format!("Species {} needs one or two types.", entry.id),
+                });
+            }
+            if entry.abilities.is_empty() {
+                return Err(DexError {
+                    message: format!("Species {} has no abilities.", entry.id),
+                });
+            }
+            if registry.contains_key(&entry.id) {
+                return Err(DexError {
+                    message: format!("Species {} is defined more than once.", entry.id),
+                });
+            }
+            registry.insert(entry.id.clone(), entry);
+        }
+        Ok(SpeciesRegistry { species: registry })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Species> {
+        self.species.get(id)
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &Species> {
+        self.species.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::battle::stats::Stats;
+    use crate::dex::species::{Species, SpeciesRegistry};
+    use crate::dex::Dex;
+
+    fn species(id: &str, types: Vec<&str>) -> Species {
+        Species {
+            id: String::from(id),
+            name: String::from(id),
+            types: types.into_iter().map(String::from).collect(),
+            base_stats: Stats::default(),
+            abilities: vec![String::from("none")],
+            learnset: vec![],
+        }
+    }
+
+    #[test]
+    fn test_loading_shipped_species() {
+        let dex = Dex::load("data").expect("The shipped species data should be valid.");
+
+        let cindercub = dex.species.get("cindercub").unwrap();
+
+        assert_eq!(cindercub.types, vec![String::from("fire")]);
+        assert!(cindercub.learnset.contains(&String::from("ember")));
+    }
+
+    #[test]
+    fn test_duplicate_species_are_rejected() {
+        let result = SpeciesRegistry::new(vec![
+            species("same", vec!["fire"]),
+            species("same", vec!["water"]),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_species_need_one_or_two_types() {
+        assert!(SpeciesRegistry::new(vec![species("typeless", vec![])]).is_err());
+        assert!(
+            SpeciesRegistry::new(vec![species("triple", vec!["fire", "water", "grass"])]).is_err()
+        );
+    }
+}
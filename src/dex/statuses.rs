@@ -0,0 +1,164 @@
+use crate::dex::{
+    moves::{BoostableStat, StatusKind},
+    DexError,
+};
+use rocket::serde::{Deserialize, Serialize};
+
+//How a major status behaves. Everything which balance patches might want to tune lives in here.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct StatusCondition {
+    pub(crate) kind: StatusKind,
+    pub(crate) end_of_turn_damage: Option<Fraction>,
+    pub(crate) stat_modifiers: Vec<StatModifier>,
+    //Chance in percent that the creature can't act in a turn.
+    pub(crate) skip_chance: u8,
+    //Chance in percent that the status is cured before the creature tries to act.
+    pub(crate) cure_chance: u8,
+    //The status wears off on its own after a random number of turns in this range.
+    pub(crate) duration: Option<TurnRange>,
+    //Being hit by a damaging move of one of these types cures the status.
+    pub(crate) cured_by_move_types: Vec<String>,
+    pub(crate) immune_types: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct Fraction {
+    pub(crate) numerator: u16,
+    pub(crate) denominator: u16,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub struct StatModifier {
+    pub(crate) stat: BoostableStat,
+    pub(crate) multiplier: f32,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Copy, Clone)]
+pub struct TurnRange {
+    pub(crate) min: u8,
+    pub(crate) max: u8,
+}
+
+impl Fraction {
+    //Never rounds down to nothing, so even creatures with very little HP are affected.
+    pub fn of(&self, value: u16) -> u16 {
+        let part = value as u32 * self.numerator as u32 / self.denominator as u32;
+        (part as u16).max(1)
+    }
+}
+
+impl StatusCondition {
+    pub fn modifier(&self, stat: BoostableStat) -> f32 {
+        self.stat_modifiers
+            .iter()
+            .filter(|modifier| modifier.stat == stat)
+            .map(|modifier| modifier.multiplier)
+            .product()
+    }
+}
+
+pub struct StatusRegistry {
+    statuses: Vec<StatusCondition>,
+}
+
+impl StatusRegistry {
+    pub fn new(statuses: Vec<StatusCondition>) -> Result<Self, DexError> {
+        for kind in StatusKind::ALL {
+            let count = statuses.iter().filter(|status| status.kind == kind).count();
+            if count != 1 {
+                return Err(DexError {
+                    message: format!("Status {:?} has to be defined exactly once.", kind),
+                });
+            }
+        }
+        for status in &statuses {
+            if status.skip_chance > 100 || status.cure_chance > 100 {
+                return Err(DexError {
+                    message: format!("Status {:?} has a chance above 100.", status.kind),
+                });
+            }
+            if status
+                .end_of_turn_damage
+                .is_some_and(|damage| damage.denominator == 0)
+            {
+                return Err(DexError {
+                    message: format!("Status {:?} divides by zero.", status.kind),
+                });
+            }
+            if status
+                .duration
+                .is_some_and(|duration| duration.min > duration.max)
+            {
+                return Err(DexError {
+                    message: format!("Status {:?} has an empty duration.", status.kind),
+                });
+            }
+            if status.stat_modifiers.iter().any(|modifier| {
+                modifier.multiplier < 0.0
+                    || matches!(
+                        modifier.stat,
+                        BoostableStat::Accuracy | BoostableStat::Evasion
+                    )
+            }) {
+                return Err(DexError {
+                    message: format!("Status {:?} has an invalid stat modifier.", status.kind),
+                });
+            }
+        }
+        Ok(StatusRegistry { statuses })
+    }
+
+    //Every kind is guaranteed to exist, the constructor checks it.
+    pub fn get(&self, kind: StatusKind) -> &StatusCondition {
+        self.statuses
+            .iter()
+            .find(|status| status.kind == kind)
+            .expect("Every status kind is validated on load.")
+    }
+
+    pub fn all(&self) -> impl Iterator<Item = &StatusCondition> {
+        self.statuses.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::moves::{BoostableStat, StatusKind};
+    use crate::dex::statuses::{Fraction, StatusRegistry};
+    use crate::dex::Dex;
+
+    #[test]
+    fn test_loading_shipped_statuses() {
+        let dex = Dex::load("data").expect("The shipped status data should be valid.");
+
+        let burn = dex.statuses.get(StatusKind::Burn);
+
+        assert_eq!(burn.modifier(BoostableStat::Attack), 0.5);
+        assert_eq!(burn.modifier(BoostableStat::Speed), 1.0);
+        assert!(burn.immune_types.contains(&String::from("fire")));
+    }
+
+    #[test]
+    fn test_missing_statuses_are_rejected() {
+        let dex = Dex::load("data").unwrap();
+        let statuses = dex
+            .statuses
+            .all()
+            .filter(|status| status.kind != StatusKind::Sleep)
+            .cloned()
+            .collect();
+
+        assert!(StatusRegistry::new(statuses).is_err());
+    }
+
+    #[test]
+    fn test_fractions_deal_at_least_one() {
+        let eighth = Fraction {
+            numerator: 1,
+            denominator: 8,
+        };
+
+        assert_eq!(eighth.of(160), 20);
+        assert_eq!(eighth.of(5), 1);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::dex::DexError;
+use rocket::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+//Matchups which are not listed in the chart are neutral.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct TypeChart {
+    pub(crate) types: Vec<String>,
+    pub(crate) effectiveness: BTreeMap<String, BTreeMap<String, f32>>,
+}
+
+impl TypeChart {
+    pub fn validate(&self) -> Result<(), DexError> {
+        for (attacking, matchups) in &self.effectiveness {
+            if !self.contains(attacking) {
+                return Err(DexError {
+                    message: format!("The type chart lists the unknown type {}.", attacking),
+                });
+            }
+            for (defending, multiplier) in matchups {
+                if !self.contains(defending) {
+                    return Err(DexError {
+                        message: format!("The type chart lists the unknown type {}.", defending),
+                    });
+                }
+                if *multiplier < 0.0 {
+                    return Err(DexError {
+                        message: format!(
+                            "The multiplier of {} against {} is negative.",
+                            attacking, defending
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, type_id: &str) -> bool {
+        self.types.iter().any(|known| known == type_id)
+    }
+
+    pub fn multiplier(&self, attacking: &str, defending: &str) -> f32 {
+        self.effectiveness
+            .get(attacking)
+            .and_then(|matchups| matchups.get(defending))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    //Dual types simply multiply the matchups against each of their types.
+    pub fn effectiveness(&self, attacking: &str, defending: &[String]) -> f32 {
+        defending
+            .iter()
+            .map(|defending| self.multiplier(attacking, defending))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dex::types::TypeChart;
+    use crate::dex::Dex;
+    use std::collections::BTreeMap;
+
+    fn types(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| String::from(*id)).collect()
+    }
+
+    #[test]
+    fn test_single_type_matchups() {
+        let chart = Dex::load("data").unwrap().types;
+
+        assert_eq!(chart.effectiveness("fire", &types(&["grass"])), 2.0);
+        assert_eq!(chart.effectiveness("fire", &types(&["water"])), 0.5);
+        assert_eq!(chart.effectiveness("normal", &types(&["ghost"])), 0.0);
+        assert_eq!(chart.effectiveness("normal", &types(&["fire"])), 1.0);
+    }
+
+    #[test]
+    fn test_dual_type_matchups() {
+        let chart = Dex::load("data").unwrap().types;
+
+        assert_eq!(
+            chart.effectiveness("ice", &types(&["grass", "ground"])),
+            4.0
+        );
+        assert_eq!(
+            chart.effectiveness("fire", &types(&["grass", "water"])),
+            1.0
+        );
+        assert_eq!(
+            chart.effectiveness("grass", &types(&["fire", "dragon"])),
+            0.25
+        );
+        assert_eq!(
+            chart.effectiveness("electric", &types(&["water", "ground"])),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_unknown_types_are_rejected() {
+        let chart = TypeChart {
+            types: types(&["fire"]),
+            effectiveness: BTreeMap::from([(
+                String::from("fire"),
+                BTreeMap::from([(String::from("plasma"), 2.0)]),
+            )]),
+        };
+
+        assert!(chart.validate().is_err());
+    }
+}
@@ -1,12 +1,16 @@
 #[macro_use]
 extern crate rocket;
 mod api;
+mod battle;
 mod db;
+mod dex;
 
-use crate::{api::get_routes, db::DbConnection};
+use crate::{api::get_routes, db::DbConnection, dex::Dex};
 use rocket::{Build, Rocket};
 use serde::Deserialize;
 
+const DATA_DIRECTORY: &str = "data";
+
 #[derive(Deserialize, Debug)]
 #[serde(crate = "rocket::serde")]
 struct Config {
@@ -27,11 +31,15 @@ async fn build_the_rocket(rocket: Rocket<Build>, config: Config) -> Rocket<Build
             .await
             .unwrap(),
         )
+        .manage(
+            Dex::load(DATA_DIRECTORY)
+                .unwrap_or_else(|err| panic!("Couldn't load the game data: {}", err.message)),
+        )
         .mount("/", get_routes())
 }
 
 #[rocket::main]
-async fn main() -> Result<(), rocket::Error> {
+async fn main() -> Result<(), Box<rocket::Error>> {
     log4rs::init_file("log4rs.yaml", Default::default()).unwrap();
 
     let rocket = rocket::build();